@@ -0,0 +1,23 @@
+use common::bench::CountingAllocator;
+use std::process;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() {
+    process::exit(common::runner::main(&[
+        &day1::DAY,
+        &day2::DAY,
+        &day3::DAY,
+        &day4::DAY,
+        &day5::DAY,
+        &day6::DAY,
+        &day7::DAY,
+        &day8::DAY,
+        &day9::DAY,
+        &day10::DAY,
+        &day11::DAY,
+        &day12::DAY,
+        &day13::DAY,
+    ]));
+}
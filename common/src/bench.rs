@@ -0,0 +1,129 @@
+use crate::input;
+use crate::report::milliseconds;
+use crate::{Error, Solver};
+use serde::{Deserialize, Serialize};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Wraps the system allocator to count allocations made while benchmarking.  Binaries opt in
+/// with `#[global_allocator]`; without it allocation counts are reported as unavailable.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+fn allocation_counters() -> (u64, u64) {
+    (
+        ALLOCATIONS.load(Ordering::Relaxed),
+        ALLOCATED_BYTES.load(Ordering::Relaxed),
+    )
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Measurement {
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub iterations: usize,
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub p95_ms: f64,
+    /// Allocations and bytes requested per iteration, when the counting allocator is installed.
+    pub allocations: Option<u64>,
+    pub allocated_bytes: Option<u64>,
+}
+
+/// Nearest-rank percentile of an already sorted sample.
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (percent * sorted.len()).div_ceil(100).max(1);
+    sorted[rank.min(sorted.len()) - 1]
+}
+
+/// Runs `solver` against the `contents` of `path` `iterations` times.  The input is read
+/// once up front so only the solver is timed.  The first error aborts the run.
+pub fn measure(
+    day: u32,
+    part: u32,
+    solver: Solver,
+    path: &Path,
+    contents: &str,
+    iterations: usize,
+) -> Result<Measurement, Error> {
+    let iterations = iterations.max(1);
+    let mut timings = Vec::with_capacity(iterations);
+    let (allocations, bytes) = allocation_counters();
+
+    for _ in 0..iterations {
+        let now = Instant::now();
+        solver(contents).map_err(|e| e.in_file(input::source(path)))?;
+        timings.push(now.elapsed());
+    }
+
+    let (allocations_after, bytes_after) = allocation_counters();
+    timings.sort_unstable();
+    let installed = INSTALLED.load(Ordering::Relaxed);
+
+    Ok(Measurement {
+        day,
+        part,
+        input: path.display().to_string(),
+        iterations,
+        min_ms: milliseconds(timings[0]),
+        median_ms: milliseconds(percentile(&timings, 50)),
+        p95_ms: milliseconds(percentile(&timings, 95)),
+        allocations: match installed {
+            true => Some((allocations_after - allocations) / iterations as u64),
+            false => None,
+        },
+        allocated_bytes: match installed {
+            true => Some((bytes_after - bytes) / iterations as u64),
+            false => None,
+        },
+    })
+}
+
+pub fn load_baseline(path: &Path) -> Result<Vec<Measurement>, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read the baseline {}: {}", path.display(), e))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| format!("Unable to parse the baseline {}: {}", path.display(), e))
+}
+
+pub fn save_baseline(path: &Path, measurements: &[Measurement]) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(measurements)
+        .map_err(|e| format!("Unable to serialize the baseline: {}", e))?;
+    fs::write(path, contents)
+        .map_err(|e| format!("Unable to write the baseline {}: {}", path.display(), e))
+}
+
+/// Percentage change in median time against the matching baseline entry, if there is one.
+pub fn change(measurement: &Measurement, baseline: &[Measurement]) -> Option<f64> {
+    baseline
+        .iter()
+        .find(|b| b.day == measurement.day && b.part == measurement.part)
+        .filter(|b| b.median_ms > 0.0)
+        .map(|b| (measurement.median_ms - b.median_ms) / b.median_ms * 100.0)
+}
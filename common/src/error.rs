@@ -0,0 +1,124 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Malformed puzzle input.  Loaders report the column and what they expected, the line is
+/// filled in by `parse_lines` and the file by whoever read the input.
+#[derive(Debug)]
+pub struct ParseError {
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new<S: Into<String>>(column: usize, text: &str, expected: S) -> Self {
+        ParseError {
+            file: None,
+            line: 0,
+            column,
+            text: text.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Builds an error for `token`, which must be a slice of `line`, so the column can be
+    /// derived from where it sits.
+    pub fn at<S: Into<String>>(line: &str, token: &str, expected: S) -> Self {
+        ParseError::new(column(line, token), token, expected)
+    }
+
+    pub fn on_line(self, line: usize) -> Self {
+        ParseError { line, ..self }
+    }
+
+    pub fn in_file(self, path: &Path) -> Self {
+        ParseError {
+            file: Some(path.to_path_buf()),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        write!(
+            f,
+            "{}:{}: found {:?} but expected {}",
+            self.line, self.column, self.text, self.expected
+        )
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[derive(Debug)]
+pub enum Error {
+    Io { path: PathBuf, source: io::Error },
+    Parse(ParseError),
+    NoSolution(String),
+}
+
+impl Error {
+    /// Attributes a parse error to the file its input was read from.
+    pub fn in_file(self, path: &Path) -> Self {
+        match self {
+            Error::Parse(e) if e.file.is_none() => Error::Parse(e.in_file(path)),
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => {
+                write!(f, "unable to read {}: {}", path.display(), source)
+            }
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::NoSolution(reason) => write!(f, "no solution found: {}", reason),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse(e) => Some(e),
+            Error::NoSolution(_) => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+/// 1-based character column of `token` within `line`.
+pub fn column(line: &str, token: &str) -> usize {
+    let offset = (token.as_ptr() as usize)
+        .saturating_sub(line.as_ptr() as usize)
+        .min(line.len());
+    line[..offset].chars().count() + 1
+}
+
+/// Parses `input` one line at a time, tagging any failure with its line number.
+pub fn parse_lines<T, F>(input: &str, parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, l)| parse(l).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
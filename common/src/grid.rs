@@ -0,0 +1,301 @@
+use crate::error::{self, ParseError};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Offsets to the eight cells surrounding a cell, clockwise from the north west.
+pub const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A value small enough to pack several into each word of a `Grid`.
+pub trait Cell: Copy {
+    /// Bits each value needs.  Must divide 64 so values never straddle two words.
+    const BITS: u32;
+    fn to_bits(self) -> u64;
+    fn from_bits(bits: u64) -> Self;
+}
+
+impl Cell for bool {
+    const BITS: u32 = 1;
+
+    fn to_bits(self) -> u64 {
+        self as u64
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        bits != 0
+    }
+}
+
+/// A rectangular grid stored row by row in a flat, bit-packed vector.  `x` runs left to right
+/// along a row and `y` top to bottom.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Grid<T: Cell> {
+    width: usize,
+    height: usize,
+    words: Vec<u64>,
+    cell: PhantomData<T>,
+}
+
+impl<T: Cell> Grid<T> {
+    const PER_WORD: usize = (64 / T::BITS) as usize;
+    const MASK: u64 = (1 << T::BITS) - 1;
+
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        let cells = width * height;
+        let mut grid = Grid {
+            width,
+            height,
+            words: vec![0; cells.div_ceil(Self::PER_WORD)],
+            cell: PhantomData,
+        };
+        if fill.to_bits() != 0 {
+            for y in 0..height {
+                for x in 0..width {
+                    grid.set(x, y, fill);
+                }
+            }
+        }
+        grid
+    }
+
+    /// Builds a grid from one row per line of `input`, read with `parse_row`.  Every row must be
+    /// as wide as the first.
+    pub fn parse<F>(input: &str, parse_row: F) -> Result<Self, ParseError>
+    where
+        F: Fn(&str) -> Result<Vec<T>, ParseError>,
+    {
+        let rows = error::parse_lines(input, parse_row)?;
+        let width = rows.first().map_or(0, |r| r.len());
+
+        if let Some((i, row)) = rows.iter().enumerate().find(|(_, r)| r.len() != width) {
+            return Err(ParseError::new(
+                row.len().min(width) + 1,
+                "",
+                format!("a row {} cells wide like the first", width),
+            )
+            .on_line(i + 1));
+        }
+
+        let mut grid = Grid::new(width, rows.len(), T::from_bits(0));
+        for (y, row) in rows.iter().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                grid.set(x, y, *value);
+            }
+        }
+        Ok(grid)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn locate(&self, x: usize, y: usize) -> (usize, u32) {
+        let index = y * self.width + x;
+        (
+            index / Self::PER_WORD,
+            (index % Self::PER_WORD) as u32 * T::BITS,
+        )
+    }
+
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let (word, shift) = self.locate(x, y);
+        Some(T::from_bits((self.words[word] >> shift) & Self::MASK))
+    }
+
+    /// Like `get`, but for coordinates which may have stepped off the top or left.
+    pub fn get_signed(&self, x: isize, y: isize) -> Option<T> {
+        if self.contains(x, y) {
+            self.get(x as usize, y as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Treats the grid as tiling the plane in every direction.  Panics on an empty grid.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        self.get(x, y).unwrap()
+    }
+
+    /// Panics if `(x, y)` is outside the grid.
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside a {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        let (word, shift) = self.locate(x, y);
+        self.words[word] =
+            (self.words[word] & !(Self::MASK << shift)) | ((value.to_bits() & Self::MASK) << shift);
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = T> + '_ {
+        (0..self.width).filter_map(move |x| self.get(x, y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = T> + '_ {
+        (0..self.height).filter_map(move |y| self.get(x, y))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = T> + '_> + '_ {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    /// Every cell with its coordinates, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        (0..self.height)
+            .flat_map(move |y| (0..self.width).map(move |x| (x, y, self.get(x, y).unwrap())))
+    }
+
+    /// The cells surrounding `(x, y)` which lie within the grid, with their coordinates.
+    pub fn neighbors(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        DIRECTIONS.iter().filter_map(move |(dx, dy)| {
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+            self.get_signed(nx, ny)
+                .map(|v| (nx as usize, ny as usize, v))
+        })
+    }
+}
+
+impl<T: Cell + PartialEq> Grid<T> {
+    pub fn count(&self, value: T) -> usize {
+        self.cells().filter(|(_, _, v)| *v == value).count()
+    }
+}
+
+impl<T: Cell + fmt::Debug> fmt::Debug for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Grid")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A cell wider than a bit, to check values don't bleed into their neighbours.
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    struct Nibble(u8);
+
+    impl Cell for Nibble {
+        const BITS: u32 = 4;
+
+        fn to_bits(self) -> u64 {
+            self.0 as u64
+        }
+
+        fn from_bits(bits: u64) -> Self {
+            Nibble(bits as u8)
+        }
+    }
+
+    fn parse_bools(input: &str) -> Result<Grid<bool>, ParseError> {
+        Grid::parse(input, |l| Ok(l.chars().map(|c| c == '#').collect()))
+    }
+
+    #[test]
+    fn values_round_trip_across_word_boundaries() {
+        // 7x5 nibbles fill more than two words, with rows starting mid-word.
+        let mut grid = Grid::new(7, 5, Nibble(0));
+        for y in 0..5 {
+            for x in 0..7 {
+                grid.set(x, y, Nibble(((x + y * 7) % 16) as u8));
+            }
+        }
+        for (x, y, value) in grid.cells() {
+            assert_eq!(value, Nibble(((x + y * 7) % 16) as u8), "({}, {})", x, y);
+        }
+
+        grid.set(3, 2, Nibble(15));
+        grid.set(3, 2, Nibble(1));
+        assert_eq!(grid.get(3, 2), Some(Nibble(1)));
+        assert_eq!(grid.get(2, 2), Some(Nibble(0)));
+        assert_eq!(grid.get(4, 2), Some(Nibble(2)));
+    }
+
+    #[test]
+    fn new_fills_every_cell() {
+        let grid = Grid::new(65, 3, true);
+        assert_eq!(grid.count(true), 65 * 3);
+        assert_eq!(Grid::new(65, 3, false).count(true), 0);
+    }
+
+    #[test]
+    fn reads_outside_the_grid_are_none() {
+        let grid = parse_bools("#.\n.#\n").unwrap();
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid.get_signed(1, 1), Some(true));
+        assert!(!grid.contains(0, -1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn writes_outside_the_grid_panic() {
+        Grid::new(2, 2, false).set(2, 0, true);
+    }
+
+    #[test]
+    fn wrapping_tiles_the_plane() {
+        let grid = parse_bools("#..\n...\n").unwrap();
+        assert!(grid.get_wrapping(3, 0));
+        assert!(grid.get_wrapping(-3, -2));
+        assert!(grid.get_wrapping(6, 4));
+        assert!(!grid.get_wrapping(-1, 0));
+    }
+
+    #[test]
+    fn neighbors_stay_within_the_grid() {
+        let grid = parse_bools("###\n###\n###\n").unwrap();
+        assert_eq!(grid.neighbors(1, 1).count(), 8);
+        assert_eq!(grid.neighbors(0, 0).count(), 3);
+        assert_eq!(grid.neighbors(2, 1).count(), 5);
+        assert_eq!(
+            grid.neighbors(0, 0)
+                .map(|(x, y, _)| (x, y))
+                .collect::<Vec<_>>(),
+            vec![(1, 0), (1, 1), (0, 1)]
+        );
+    }
+
+    #[test]
+    fn rows_and_columns_read_in_order() {
+        let grid = parse_bools("#..\n.#.\n").unwrap();
+        assert_eq!(grid.row(0).collect::<Vec<_>>(), vec![true, false, false]);
+        assert_eq!(grid.column(1).collect::<Vec<_>>(), vec![false, true]);
+        assert_eq!(grid.rows().count(), 2);
+    }
+
+    #[test]
+    fn ragged_rows_are_rejected() {
+        let e = parse_bools("#..\n.#\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+    }
+}
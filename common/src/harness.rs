@@ -0,0 +1,104 @@
+use crate::input::{self, DataDir};
+use crate::{Answer, Day, Error, Example, Part};
+use std::panic;
+use std::path::{Path, PathBuf};
+
+pub enum Outcome {
+    Success,
+    Failed(Answer),
+    Errored(Error),
+    Panicked,
+    /// The part has no solver yet, so there is nothing to check.
+    Unsolved,
+    /// The example's input couldn't be read.
+    NoInput(Error),
+}
+
+pub struct Calibration<'a> {
+    pub day: &'a Day,
+    pub example: &'a Example,
+    pub path: PathBuf,
+    pub outcome: Outcome,
+}
+
+impl Calibration<'_> {
+    pub fn succeeded(&self) -> bool {
+        matches!(self.outcome, Outcome::Success)
+    }
+
+    /// Whether the solver ran against the example, whatever it answered.
+    pub fn ran(&self) -> bool {
+        !matches!(self.outcome, Outcome::Unsolved | Outcome::NoInput(_))
+    }
+
+    pub fn implemented(&self) -> bool {
+        !matches!(self.outcome, Outcome::Unsolved)
+    }
+}
+
+fn check(day: &Day, example: &Example, path: &Path) -> Outcome {
+    let solver = match example.solver.or_else(|| day.solver(example.part)) {
+        Some(solver) => solver,
+        None => return Outcome::Unsolved,
+    };
+
+    let contents = match input::read(path) {
+        Ok(contents) => contents,
+        Err(e) => return Outcome::NoInput(e),
+    };
+
+    match panic::catch_unwind(|| solver(&contents).map_err(|e| e.in_file(path))) {
+        Ok(Ok(value)) if value == example.expected => Outcome::Success,
+        Ok(Ok(value)) => Outcome::Failed(value),
+        Ok(Err(e)) => Outcome::Errored(e),
+        Err(_) => Outcome::Panicked,
+    }
+}
+
+/// Runs every registered example for `day`, optionally restricted to the given parts.
+pub fn calibrate<'a>(day: &'a Day, parts: &[Part], data: &DataDir) -> Vec<Calibration<'a>> {
+    day.examples
+        .iter()
+        .filter(|e| parts.contains(&e.part))
+        .map(|example| {
+            let path = data.resolve(day.number, example.input);
+            Calibration {
+                day,
+                example,
+                outcome: check(day, example, &path),
+                path,
+            }
+        })
+        .collect()
+}
+
+pub fn print_calibration(calibration: &Calibration) {
+    let example = calibration.example;
+    let name = input::display_name(&calibration.path);
+    match &calibration.outcome {
+        Outcome::Success => println!(
+            "Part {0} - {1} => {2} [SUCCESS]",
+            example.part, name, example.expected
+        ),
+        Outcome::Failed(value) => println!(
+            "Part {0} - {1} => {2} [FAILED]  Expected {3}",
+            example.part, name, value, example.expected
+        ),
+        Outcome::Errored(e) => println!(
+            "Part {0} - {1} => {2} [FAILED]  Expected {3}",
+            example.part, name, e, example.expected
+        ),
+        Outcome::Panicked => println!(
+            "Part {0} - {1} => panicked [FAILED]  Expected {2}",
+            example.part, name, example.expected
+        ),
+        Outcome::Unsolved => println!(
+            "Part {0} - {1} => not implemented [SKIPPED]  Expected {2}",
+            example.part, name, example.expected
+        ),
+        Outcome::NoInput(e) => println!(
+            "Part {0} - {1} => {2} [FAILED]  Expected {3}",
+            example.part, name, e, example.expected
+        ),
+    }
+}
@@ -0,0 +1,124 @@
+use crate::Error;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+
+pub const DATA_DIR_VAR: &str = "AOC_DATA_DIR";
+
+/// Passing this as the input path reads the puzzle from standard input.
+pub const STDIN: &str = "-";
+
+/// Which file in the data directory a solver should read.
+#[derive(Copy, Clone, Debug)]
+pub enum Input {
+    /// The puzzle input, `day{N}.txt`.
+    Puzzle,
+    /// A numbered example, `day{N}.example.{k}.txt`.  Days with a single example use
+    /// `day{N}.example.txt`, which is picked up when asking for example 1.
+    Example(u32),
+    /// Any other file in the data directory, e.g. `day4.part2.valid.txt`.
+    File(&'static str),
+}
+
+pub struct DataDir {
+    root: PathBuf,
+}
+
+impl DataDir {
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        DataDir { root: root.into() }
+    }
+
+    /// Picks the data directory from the command line, then `AOC_DATA_DIR`, then `Data` in
+    /// the working directory, finally falling back to the `Data` folder of this checkout.
+    pub fn locate(flag: Option<PathBuf>) -> Self {
+        if let Some(root) = flag {
+            return DataDir::new(root);
+        }
+
+        if let Some(root) = env::var_os(DATA_DIR_VAR) {
+            return DataDir::new(root);
+        }
+
+        let local = PathBuf::from("Data");
+        if local.is_dir() {
+            DataDir::new(local)
+        } else {
+            DataDir::new(
+                Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("..")
+                    .join("Data"),
+            )
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn resolve(&self, day: u32, input: Input) -> PathBuf {
+        match input {
+            Input::Puzzle => self.root.join(format!("day{}.txt", day)),
+            Input::Example(k) => {
+                let numbered = self.root.join(format!("day{}.example.{}.txt", day, k));
+                let single = self.root.join(format!("day{}.example.txt", day));
+                if k == 1 && !numbered.is_file() && single.is_file() {
+                    single
+                } else {
+                    numbered
+                }
+            }
+            Input::File(name) => self.root.join(name),
+        }
+    }
+}
+
+pub fn is_stdin(path: &Path) -> bool {
+    path == Path::new(STDIN)
+}
+
+/// Whether `path` names something `read` can load.
+pub fn exists(path: &Path) -> bool {
+    is_stdin(path) || path.is_file()
+}
+
+pub fn read_from<R: BufRead>(mut reader: R, path: &Path) -> Result<String, Error> {
+    let mut contents = String::new();
+    reader
+        .read_to_string(&mut contents)
+        .map_err(|source| Error::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    Ok(contents)
+}
+
+/// Loads the puzzle input at `path`, or standard input when given `-`.
+pub fn read(path: &Path) -> Result<String, Error> {
+    if is_stdin(path) {
+        read_from(io::stdin().lock(), source(path))
+    } else {
+        fs::read_to_string(path).map_err(|source| Error::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+}
+
+/// How errors should refer to the input at `path`.
+pub fn source(path: &Path) -> &Path {
+    if is_stdin(path) {
+        Path::new("<stdin>")
+    } else {
+        path
+    }
+}
+
+/// The file name portion of `path` for reports, or the whole path if it has none.
+pub fn display_name(path: &Path) -> String {
+    let path = source(path);
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
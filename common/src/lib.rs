@@ -0,0 +1,70 @@
+pub mod bench;
+pub mod error;
+pub mod grid;
+pub mod harness;
+pub mod input;
+pub mod report;
+pub mod runner;
+
+pub use error::{Error, ParseError};
+pub use input::Input;
+
+use std::fmt;
+
+/// Every puzzle in the calendar boils down to a single integer answer.
+pub type Answer = i64;
+
+/// Solvers receive the whole puzzle input so they can be driven from files, stdin or tests.
+pub type Solver = fn(&str) -> Result<Answer, Error>;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// A worked example from the puzzle text along with the answer it is known to produce.
+///
+/// `solver` overrides the day's solver for examples that need different tuning than the real
+/// input (e.g. a shorter preamble).
+pub struct Example {
+    pub part: Part,
+    pub input: Input,
+    pub expected: Answer,
+    pub solver: Option<Solver>,
+}
+
+/// Registration record each day crate exposes so the runner can find its solvers.
+pub struct Day {
+    pub number: u32,
+    pub title: &'static str,
+    pub part1: Option<Solver>,
+    pub part2: Option<Solver>,
+    pub examples: &'static [Example],
+}
+
+impl Day {
+    pub fn solver(&self, part: Part) -> Option<Solver> {
+        match part {
+            Part::One => self.part1,
+            Part::Two => self.part2,
+        }
+    }
+}
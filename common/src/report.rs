@@ -0,0 +1,162 @@
+use crate::bench::Measurement;
+use crate::harness::{Calibration, Outcome};
+use crate::input;
+use crate::Answer;
+use serde::Serialize;
+use std::time::Duration;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl Format {
+    pub fn parse(value: &str) -> Result<Format, String> {
+        match value {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            x => Err(format!("Unknown format {}", x)),
+        }
+    }
+}
+
+/// How the worked examples for a part fared before it was run against the real input.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CalibrationStatus {
+    Passed,
+    Failed,
+    None,
+    Skipped,
+}
+
+impl CalibrationStatus {
+    /// Examples which couldn't be run count towards neither passing nor failing.
+    pub fn of(calibrations: &[Calibration]) -> Self {
+        if !calibrations.iter().any(|c| c.ran()) {
+            CalibrationStatus::None
+        } else if calibrations
+            .iter()
+            .filter(|c| c.ran())
+            .all(|c| c.succeeded())
+        {
+            CalibrationStatus::Passed
+        } else {
+            CalibrationStatus::Failed
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PartResult {
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub answer: Option<Answer>,
+    pub error: Option<String>,
+    pub elapsed_ms: f64,
+    pub calibration: CalibrationStatus,
+}
+
+#[derive(Serialize)]
+pub struct ExampleResult {
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub expected: Answer,
+    pub answer: Option<Answer>,
+    pub error: Option<String>,
+    pub success: bool,
+}
+
+impl From<&Calibration<'_>> for ExampleResult {
+    fn from(calibration: &Calibration) -> Self {
+        let (answer, error) = match &calibration.outcome {
+            Outcome::Success => (Some(calibration.example.expected), None),
+            Outcome::Failed(value) => (Some(*value), None),
+            Outcome::Errored(e) => (None, Some(e.to_string())),
+            Outcome::Panicked => (None, Some("the solver panicked".to_string())),
+            Outcome::Unsolved => (None, Some("not implemented".to_string())),
+            Outcome::NoInput(e) => (None, Some(e.to_string())),
+        };
+
+        ExampleResult {
+            day: calibration.day.number,
+            part: calibration.example.part.number(),
+            input: calibration.path.display().to_string(),
+            expected: calibration.example.expected,
+            answer,
+            error,
+            success: calibration.succeeded(),
+        }
+    }
+}
+
+pub fn milliseconds(elapsed: Duration) -> f64 {
+    elapsed.as_secs_f64() * 1000.0
+}
+
+pub fn print_json<T: Serialize>(record: &T) {
+    match serde_json::to_string(record) {
+        Ok(line) => println!("{}", line),
+        Err(e) => eprintln!("Unable to serialize the result: {}", e),
+    }
+}
+
+pub fn print_part(result: &PartResult) {
+    let name = input::display_name(std::path::Path::new(&result.input));
+    match (&result.answer, &result.error) {
+        (Some(answer), _) => println!(
+            "Part {0} - {1}\t{2}\t{3:.3}ms\texamples {4}",
+            result.part,
+            answer,
+            name,
+            result.elapsed_ms,
+            match result.calibration {
+                CalibrationStatus::Passed => "passed",
+                CalibrationStatus::Failed => "FAILED",
+                CalibrationStatus::None => "unavailable",
+                CalibrationStatus::Skipped => "skipped",
+            }
+        ),
+        (None, Some(e)) => eprintln!("Part {} - error: {}", result.part, e),
+        (None, None) => println!("Part {} - not implemented", result.part),
+    }
+}
+
+#[derive(Serialize)]
+pub struct BenchResult<'a> {
+    #[serde(flatten)]
+    pub measurement: &'a Measurement,
+    pub change_percent: Option<f64>,
+    pub regression: bool,
+}
+
+pub fn print_measurement(result: &BenchResult) {
+    let m = result.measurement;
+    println!(
+        "Part {0} - min {1:.3}ms\tmedian {2:.3}ms\tp95 {3:.3}ms\t{4}\t{5} over {6} runs",
+        m.part,
+        m.min_ms,
+        m.median_ms,
+        m.p95_ms,
+        match (m.allocations, m.allocated_bytes) {
+            (Some(count), Some(bytes)) => format!("{} allocations ({} bytes)", count, bytes),
+            _ => "allocations unavailable".to_string(),
+        },
+        input::display_name(std::path::Path::new(&m.input)),
+        m.iterations
+    );
+    if let Some(change) = result.change_percent {
+        println!(
+            "         {0:+.1}% against the baseline median{1}",
+            change,
+            if result.regression {
+                " [REGRESSION]"
+            } else {
+                ""
+            }
+        );
+    }
+}
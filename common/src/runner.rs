@@ -0,0 +1,677 @@
+use crate::bench::{self, Measurement};
+use crate::harness;
+use crate::input::{self, DataDir, Input};
+use crate::report::{self, BenchResult, CalibrationStatus, ExampleResult, Format, PartResult};
+use crate::{Answer, Day, Error, ParseError, Part, Solver};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const USAGE: &str =
+    "Usage: aoc [run|check|bench] [DAY|all] [--part 1|2|both] [--input PATH|--example K]
+                [--data-dir PATH] [--format text|json] [--skip-check] [--timeout SECONDS]
+                [--iterations N] [--save PATH] [--baseline PATH] [--threshold PERCENT]
+
+    run             solve the puzzle input (default)
+    check           solve the worked examples and compare against the expected answers
+    bench           time repeated runs of each part against the puzzle input
+    DAY             day number to run, defaults to every registered day
+    --part          which part to solve, defaults to both
+    --input         puzzle input to use instead of day{DAY}.txt, or - to read from stdin
+    --example       run against day{DAY}.example.{K}.txt from the data directory
+    --data-dir      folder holding the inputs, defaults to $AOC_DATA_DIR or Data
+    --format        text for people or json for one object per line, defaults to text
+    --skip-check    don't run a part's examples before solving it
+    --timeout       how long run waits for a part's answer before moving on, 0 to wait
+                    forever, defaults to 60
+    --iterations    how many times bench runs each part, defaults to 10
+    --save          write the bench measurements to PATH for later comparison
+    --baseline      compare bench medians against measurements saved with --save
+    --threshold     percentage slowdown bench flags as a regression, defaults to 10";
+
+#[derive(Eq, PartialEq)]
+enum Command {
+    Run,
+    Check,
+    Bench,
+}
+
+struct Options {
+    command: Command,
+    day: Option<u32>,
+    parts: Vec<Part>,
+    input: Option<PathBuf>,
+    example: Option<u32>,
+    data_dir: Option<PathBuf>,
+    format: Format,
+    calibrate: bool,
+    timeout: Option<Duration>,
+    iterations: usize,
+    save: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+}
+
+fn parse_part(value: &str) -> Result<Vec<Part>, String> {
+    match value {
+        "1" => Ok(vec![Part::One]),
+        "2" => Ok(vec![Part::Two]),
+        "both" => Ok(Part::ALL.to_vec()),
+        x => Err(format!("Unknown part {}", x)),
+    }
+}
+
+/// Reads the command line for a binary registering `days`.  A binary with a single day runs it
+/// without the day number being given.
+fn parse_args<I>(args: I, days: &[&Day]) -> Result<Options, String>
+where
+    I: Iterator<Item = String>,
+{
+    let (shared, args) = CommandOptions::extract(&args.collect::<Vec<_>>())?;
+    let mut options = Options {
+        command: Command::Run,
+        day: None,
+        parts: Part::ALL.to_vec(),
+        input: shared.input,
+        example: None,
+        data_dir: shared.data_dir,
+        format: shared.format,
+        calibrate: true,
+        timeout: Some(Duration::from_secs(60)),
+        iterations: 10,
+        save: None,
+        baseline: None,
+        threshold: 10.0,
+    };
+    let mut args = args.into_iter().peekable();
+
+    match args.peek().map(|a| a.as_str()) {
+        Some("run") => {
+            args.next();
+        }
+        Some("check") => {
+            options.command = Command::Check;
+            args.next();
+        }
+        Some("bench") => {
+            options.command = Command::Bench;
+            args.next();
+        }
+        _ => (),
+    }
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                let value = args.next().ok_or("--part requires a value")?;
+                options.parts = parse_part(&value)?;
+            }
+            "--example" | "-e" => {
+                let value = args.next().ok_or("--example requires a value")?;
+                options.example = Some(
+                    value
+                        .parse::<u32>()
+                        .map_err(|_| format!("{} is not an example number", value))?,
+                );
+            }
+            "--skip-check" => options.calibrate = false,
+            "--timeout" => {
+                let value = args.next().ok_or("--timeout requires a value")?;
+                let seconds = value
+                    .parse::<u64>()
+                    .map_err(|_| format!("{} is not a number of seconds", value))?;
+                options.timeout = Some(Duration::from_secs(seconds)).filter(|t| !t.is_zero());
+            }
+            "--iterations" | "-n" => {
+                let value = args.next().ok_or("--iterations requires a value")?;
+                options.iterations = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| format!("{} is not a positive number of iterations", value))?;
+            }
+            "--save" => {
+                let value = args.next().ok_or("--save requires a value")?;
+                options.save = Some(PathBuf::from(value));
+            }
+            "--baseline" => {
+                let value = args.next().ok_or("--baseline requires a value")?;
+                options.baseline = Some(PathBuf::from(value));
+            }
+            "--threshold" => {
+                let value = args.next().ok_or("--threshold requires a value")?;
+                options.threshold = value
+                    .parse::<f64>()
+                    .map_err(|_| format!("{} is not a percentage", value))?;
+            }
+            "all" => options.day = None,
+            x if x.starts_with('-') => return Err(format!("Unknown option {}", x)),
+            x => {
+                options.day = Some(
+                    x.parse::<u32>()
+                        .map_err(|_| format!("{} is not a day number", x))?,
+                )
+            }
+        }
+    }
+
+    if let [day] = days {
+        options.day.get_or_insert(day.number);
+    }
+
+    let specific = options.input.is_some() || options.example.is_some();
+
+    if options.input.is_some() && options.example.is_some() {
+        return Err("--input and --example cannot be combined".to_string());
+    }
+
+    if specific && options.day.is_none() {
+        return Err("--input and --example can only be used when running a single day".to_string());
+    }
+
+    if (options.save.is_some() || options.baseline.is_some()) && options.command != Command::Bench {
+        return Err("--save and --baseline can only be used with bench".to_string());
+    }
+
+    if specific && options.command == Command::Check {
+        return Err("--input and --example cannot be used with check".to_string());
+    }
+
+    Ok(options)
+}
+
+/// The options a day's own subcommands share: where the input comes from and how to print.
+pub struct CommandOptions {
+    pub input: Option<PathBuf>,
+    pub data_dir: Option<PathBuf>,
+    pub format: Format,
+}
+
+impl CommandOptions {
+    /// Takes the shared options out of `args`, leaving the rest for the day to read.
+    fn extract(args: &[String]) -> Result<(CommandOptions, Vec<String>), String> {
+        let mut options = CommandOptions {
+            input: None,
+            data_dir: None,
+            format: Format::Text,
+        };
+        let mut rest = vec![];
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" | "-i" => {
+                    let value = args.next().ok_or("--input requires a value")?;
+                    options.input = Some(PathBuf::from(value));
+                }
+                "--data-dir" | "-d" => {
+                    let value = args.next().ok_or("--data-dir requires a value")?;
+                    options.data_dir = Some(PathBuf::from(value));
+                }
+                "--format" | "-f" => {
+                    let value = args.next().ok_or("--format requires a value")?;
+                    options.format = Format::parse(value)?;
+                }
+                _ => rest.push(arg.clone()),
+            }
+        }
+
+        Ok((options, rest))
+    }
+
+    /// The input to read: the one given with `--input`, or `day`'s puzzle in the data directory.
+    pub fn path(&self, day: &Day) -> PathBuf {
+        match &self.input {
+            Some(path) => path.clone(),
+            None => DataDir::locate(self.data_dir.clone()).resolve(day.number, Input::Puzzle),
+        }
+    }
+
+    /// Reads `day`'s input and parses it, attributing any parse error to the file it came from.
+    pub fn load<T, F>(&self, day: &Day, parse: F) -> Result<T, Error>
+    where
+        F: FnOnce(&str) -> Result<T, ParseError>,
+    {
+        let path = self.path(day);
+        let contents = input::read(&path)?;
+        parse(&contents).map_err(|e| e.in_file(input::source(&path)).into())
+    }
+}
+
+/// `usage` followed by the help for the shared options.
+fn command_usage(day: &Day, usage: &str) -> String {
+    format!(
+        "{}
+
+Options: [--input PATH] [--data-dir PATH] [--format text|json]
+
+    --input         puzzle input to use instead of day{}.txt, or - to read from stdin
+    --data-dir      folder holding the inputs, defaults to $AOC_DATA_DIR or Data
+    --format        text for people or json, defaults to text",
+        usage, day.number
+    )
+}
+
+/// Entry point for a day's own subcommands.  Handles `--help` and the shared options, reads the
+/// rest with `parse` and hands the result to `run`.  Returns the process exit code: 2 for a
+/// usage error and 1 if `run` fails, after printing the message.
+pub fn command<T, P, R>(day: &Day, usage: &str, args: &[String], parse: P, run: R) -> i32
+where
+    P: FnOnce(&[String]) -> Result<T, String>,
+    R: FnOnce(T, &CommandOptions) -> Result<(), String>,
+{
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", command_usage(day, usage));
+        return 0;
+    }
+
+    let (shared, options) = match CommandOptions::extract(args)
+        .and_then(|(shared, rest)| Ok((shared, parse(&rest)?)))
+    {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, command_usage(day, usage));
+            return 2;
+        }
+    };
+
+    match run(options, &shared) {
+        Ok(()) => 0,
+        Err(message) => {
+            eprintln!("{}", message);
+            1
+        }
+    }
+}
+
+fn print_header(day: &Day, options: &Options) {
+    if options.format == Format::Text {
+        println!("Day {} - {}", day.number, day.title);
+        println!("=============");
+    }
+}
+
+fn print_footer(options: &Options) {
+    if options.format == Format::Text {
+        println!();
+    }
+}
+
+fn run_part(
+    day: &Day,
+    part: Part,
+    path: &Path,
+    contents: &Result<String, String>,
+    options: &Options,
+    data: &DataDir,
+) -> PartResult {
+    let calibration = match options.calibrate {
+        true => CalibrationStatus::of(&harness::calibrate(day, &[part], data)),
+        false => CalibrationStatus::Skipped,
+    };
+
+    let mut result = PartResult {
+        day: day.number,
+        part: part.number(),
+        input: path.display().to_string(),
+        answer: None,
+        error: None,
+        elapsed_ms: 0.0,
+        calibration,
+    };
+
+    match (day.solver(part), contents) {
+        (None, _) => (),
+        (Some(_), Err(e)) => result.error = Some(e.clone()),
+        (Some(solver), Ok(contents)) => {
+            let contents = contents.clone();
+            let now = Instant::now();
+            let answer = solve_within(solver, contents, options.timeout);
+            result.elapsed_ms = report::milliseconds(now.elapsed());
+            match answer {
+                Ok(Ok(answer)) => result.answer = Some(answer),
+                Ok(Err(e)) => result.error = Some(e.in_file(input::source(path)).to_string()),
+                Err(message) => result.error = Some(message),
+            }
+        }
+    }
+
+    result
+}
+
+/// Solves on a thread of its own so the run can move on once `timeout` passes.  A solver that
+/// overruns keeps working in the background until the process exits, so later parts share the
+/// machine with it.
+fn solve_within(
+    solver: Solver,
+    contents: String,
+    timeout: Option<Duration>,
+) -> Result<Result<Answer, Error>, String> {
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return Ok(solver(&contents)),
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || sender.send(solver(&contents)));
+    receiver.recv_timeout(timeout).map_err(|e| match e {
+        RecvTimeoutError::Timeout => format!("gave up after {}s", timeout.as_secs()),
+        RecvTimeoutError::Disconnected => "the solver panicked".to_string(),
+    })
+}
+
+fn input_path(day: &Day, options: &Options, data: &DataDir) -> PathBuf {
+    match (&options.input, options.example) {
+        (Some(path), _) => path.clone(),
+        (None, Some(k)) => data.resolve(day.number, Input::Example(k)),
+        (None, None) => data.resolve(day.number, Input::Puzzle),
+    }
+}
+
+fn run_day(day: &Day, options: &Options, data: &DataDir) -> bool {
+    print_header(day, options);
+
+    let path = input_path(day, options, data);
+    // Read once up front: stdin can only be consumed a single time.
+    let contents = input::read(&path).map_err(|e| e.to_string());
+
+    let mut success = true;
+    for part in options.parts.iter() {
+        let implemented = day.solver(*part).is_some();
+        let result = run_part(day, *part, &path, &contents, options, data);
+        success &= result.error.is_none() && result.calibration != CalibrationStatus::Failed;
+
+        match options.format {
+            Format::Text => report::print_part(&result),
+            Format::Json if implemented => report::print_json(&result),
+            Format::Json => (),
+        }
+    }
+    print_footer(options);
+
+    success
+}
+
+/// Returns the number of parts which failed to run or regressed against the baseline.
+fn bench_day(
+    day: &Day,
+    options: &Options,
+    data: &DataDir,
+    baseline: &[Measurement],
+    measurements: &mut Vec<Measurement>,
+) -> usize {
+    print_header(day, options);
+
+    let path = input_path(day, options, data);
+    let contents = input::read(&path);
+    let mut failures = 0;
+
+    for part in options.parts.iter() {
+        let solver = match day.solver(*part) {
+            Some(solver) => solver,
+            None => {
+                if options.format == Format::Text {
+                    println!("Part {} - not implemented", part);
+                }
+                continue;
+            }
+        };
+
+        let contents = match &contents {
+            Ok(contents) => contents,
+            Err(e) => {
+                eprintln!("Part {} - error: {}", part, e);
+                failures += 1;
+                continue;
+            }
+        };
+
+        match bench::measure(
+            day.number,
+            part.number(),
+            solver,
+            &path,
+            contents,
+            options.iterations,
+        ) {
+            Ok(measurement) => {
+                let change = bench::change(&measurement, baseline);
+                let result = BenchResult {
+                    measurement: &measurement,
+                    change_percent: change,
+                    regression: change.is_some_and(|c| c > options.threshold),
+                };
+                match options.format {
+                    Format::Text => report::print_measurement(&result),
+                    Format::Json => report::print_json(&result),
+                }
+                if result.regression {
+                    failures += 1;
+                }
+                measurements.push(measurement);
+            }
+            Err(e) => {
+                eprintln!("Part {} - error: {}", part, e);
+                failures += 1;
+            }
+        }
+    }
+    print_footer(options);
+
+    failures
+}
+
+/// Returns the number of examples with a solver to check and the number which matched.  Examples whose input
+/// is missing are reported but not counted.
+fn check_day(day: &Day, options: &Options, data: &DataDir) -> (usize, usize) {
+    print_header(day, options);
+
+    let calibrations = harness::calibrate(day, &options.parts, data);
+    for calibration in calibrations.iter() {
+        match options.format {
+            Format::Text => harness::print_calibration(calibration),
+            Format::Json => report::print_json(&ExampleResult::from(calibration)),
+        }
+    }
+    if calibrations.is_empty() && options.format == Format::Text {
+        println!("No examples registered");
+    }
+    print_footer(options);
+
+    (
+        calibrations.iter().filter(|c| c.implemented()).count(),
+        calibrations.iter().filter(|c| c.succeeded()).count(),
+    )
+}
+
+/// Entry point shared by the `aoc` binary and each day's own binary.  Returns the process exit code.
+pub fn main(days: &[&Day]) -> i32 {
+    execute(env::args().skip(1).collect(), days, "")
+}
+
+/// Like `main`, for a day's binary which also has subcommands of its own.  `--help` lists
+/// `commands` after the runner's options.
+pub fn main_with_commands(days: &[&Day], commands: &str) -> i32 {
+    execute(env::args().skip(1).collect(), days, commands)
+}
+
+fn execute(args: Vec<String>, days: &[&Day], commands: &str) -> i32 {
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", USAGE);
+        if !commands.is_empty() {
+            println!(
+                "\nCommands of this day, each with its own --help:\n{}",
+                commands
+            );
+        }
+        return 0;
+    }
+
+    let options = match parse_args(args.into_iter(), days) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            return 2;
+        }
+    };
+
+    let selected: Vec<&&Day> = days
+        .iter()
+        .filter(|d| options.day.is_none_or(|n| d.number == n))
+        .collect();
+
+    if selected.is_empty() {
+        eprintln!("Day {} is not available", options.day.unwrap_or_default());
+        return 2;
+    }
+
+    let data = DataDir::locate(options.data_dir.clone());
+    if options.input.is_none() && !data.root().is_dir() {
+        eprintln!(
+            "The data directory {} does not exist",
+            data.root().display()
+        );
+        return 2;
+    }
+
+    let failures = match options.command {
+        Command::Run => selected
+            .iter()
+            .filter(|d| !run_day(d, &options, &data))
+            .count(),
+        Command::Check => {
+            let (total, succeeded) = selected
+                .iter()
+                .map(|d| check_day(d, &options, &data))
+                .fold((0, 0), |acc, (t, s)| (acc.0 + t, acc.1 + s));
+            if options.format == Format::Text {
+                println!("{} of {} calibrations succeeded", succeeded, total);
+            }
+            if total == 0 {
+                eprintln!("No examples were checked");
+                return 1;
+            }
+            total - succeeded
+        }
+        Command::Bench => {
+            let baseline = match &options.baseline {
+                Some(path) => match bench::load_baseline(path) {
+                    Ok(baseline) => baseline,
+                    Err(message) => {
+                        eprintln!("{}", message);
+                        return 2;
+                    }
+                },
+                None => vec![],
+            };
+
+            let mut measurements = vec![];
+            let failures = selected
+                .iter()
+                .map(|d| bench_day(d, &options, &data, &baseline, &mut measurements))
+                .sum::<usize>();
+
+            if let Some(path) = &options.save {
+                if let Err(message) = bench::save_baseline(path, &measurements) {
+                    eprintln!("{}", message);
+                    return 2;
+                }
+            }
+            failures
+        }
+    };
+
+    match failures {
+        0 => 0,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Example;
+    use std::fs;
+    use std::process;
+
+    static DAY: Day = Day {
+        number: 99,
+        title: "Test",
+        part1: Some(|input| Ok(input.lines().count() as Answer)),
+        part2: None,
+        examples: &[
+            Example {
+                part: Part::One,
+                input: Input::Example(1),
+                expected: 2,
+                solver: None,
+            },
+            Example {
+                part: Part::Two,
+                input: Input::Example(1),
+                expected: 2,
+                solver: None,
+            },
+        ],
+    };
+
+    /// An empty data directory of its own for each test.
+    fn data_dir(name: &str) -> DataDir {
+        let root = env::temp_dir().join(format!("aoc-{}-{}", name, process::id()));
+        fs::create_dir_all(&root).unwrap();
+        DataDir::new(root)
+    }
+
+    fn check(data: &DataDir, part: &str) -> i32 {
+        let args = ["check", "--part", part, "--data-dir"]
+            .iter()
+            .map(|a| a.to_string())
+            .chain(Some(data.root().display().to_string()))
+            .collect();
+        execute(args, &[&DAY], "")
+    }
+
+    #[test]
+    fn slow_solvers_are_given_up_on() {
+        let slow: Solver = |_| {
+            thread::sleep(Duration::from_secs(5));
+            Ok(1)
+        };
+        let timeout = Some(Duration::from_millis(50));
+        assert!(solve_within(slow, String::new(), timeout)
+            .unwrap_err()
+            .starts_with("gave up"));
+
+        let quick: Solver = |input| Ok(input.len() as Answer);
+        assert!(matches!(
+            solve_within(quick, "abc".to_string(), timeout),
+            Ok(Ok(3))
+        ));
+        assert!(matches!(
+            solve_within(quick, "abc".to_string(), None),
+            Ok(Ok(3))
+        ));
+    }
+
+    #[test]
+    fn check_fails_on_a_missing_example() {
+        let data = data_dir("missing-example");
+        assert_eq!(check(&data, "1"), 1);
+        assert_eq!(check(&data, "both"), 1);
+
+        fs::write(data.resolve(99, Input::Example(1)), "a\nb\n").unwrap();
+        assert_eq!(check(&data, "1"), 0);
+        assert_eq!(check(&data, "both"), 0);
+        fs::remove_dir_all(data.root()).unwrap();
+    }
+
+    #[test]
+    fn check_fails_when_no_example_has_a_solver() {
+        let data = data_dir("unsolved");
+        fs::write(data.resolve(99, Input::Example(1)), "a\nb\n").unwrap();
+        assert_eq!(check(&data, "2"), 1);
+        fs::remove_dir_all(data.root()).unwrap();
+    }
+}
@@ -0,0 +1,108 @@
+use common::error;
+use common::{Answer, Day, Error, Example, Input, ParseError, Part};
+
+const TARGET: i64 = 2020;
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    error::parse_lines(input, |l| {
+        l.parse::<i64>()
+            .map_err(|_| ParseError::new(1, l, "an expense amount"))
+    })
+}
+
+/// Two entries of an ascending slice summing to `target`, closing in from both ends.
+fn pair_sum(sorted: &[i64], target: i64) -> Option<Vec<i64>> {
+    if sorted.len() < 2 {
+        return None;
+    }
+
+    let (mut low, mut high) = (0, sorted.len() - 1);
+    while low < high {
+        let sum = sorted[low] + sorted[high];
+        if sum == target {
+            return Some(vec![sorted[low], sorted[high]]);
+        } else if sum < target {
+            low += 1;
+        } else {
+            high -= 1;
+        }
+    }
+
+    None
+}
+
+fn k_sum_sorted(sorted: &[i64], target: i64, k: usize) -> Option<Vec<i64>> {
+    match k {
+        0 => None,
+        1 => sorted.binary_search(&target).ok().map(|_| vec![target]),
+        2 => pair_sum(sorted, target),
+        _ => {
+            for (i, first) in sorted.iter().enumerate() {
+                // Skip repeated values, they'd only repeat the search.
+                if i > 0 && sorted[i - 1] == *first {
+                    continue;
+                }
+                let rest = &sorted[i + 1..];
+                if let Some(mut found) = k_sum_sorted(rest, target - first, k - 1) {
+                    found.insert(0, *first);
+                    return Some(found);
+                }
+            }
+            None
+        }
+    }
+}
+
+/// Finds `k` distinct entries summing to `target`, smallest first.  Sorting once lets pairs be
+/// found in a single pass, so the search is O(n^(k-1)) rather than O(n^k).
+pub fn k_sum(entries: &[i64], target: i64, k: usize) -> Option<Vec<i64>> {
+    let mut sorted = entries.to_vec();
+    sorted.sort_unstable();
+    k_sum_sorted(&sorted, target, k)
+}
+
+/// The product of the `k` entries summing to 2020.
+pub fn solve(entries: &[i64], k: usize) -> Option<i64> {
+    k_sum(entries, TARGET, k).map(|found| found.iter().product())
+}
+
+pub fn part1(entries: &[i64]) -> Option<i64> {
+    solve(entries, 2)
+}
+
+pub fn part2(entries: &[i64]) -> Option<i64> {
+    solve(entries, 3)
+}
+
+fn no_solution(k: usize) -> Error {
+    Error::NoSolution(format!("no {} entries sum to {}", k, TARGET))
+}
+
+fn solve_part1(input: &str) -> Result<Answer, Error> {
+    part1(&parse(input)?).ok_or_else(|| no_solution(2))
+}
+
+fn solve_part2(input: &str) -> Result<Answer, Error> {
+    part2(&parse(input)?).ok_or_else(|| no_solution(3))
+}
+
+pub static DAY: Day = Day {
+    number: 1,
+    title: "Report Repair",
+    part1: Some(solve_part1),
+    part2: Some(solve_part2),
+    examples: &[
+        Example {
+            part: Part::One,
+            input: Input::Example(1),
+            expected: 514579,
+            solver: None,
+        },
+        Example {
+            part: Part::Two,
+            input: Input::Example(1),
+            expected: 241861950,
+            solver: None,
+        },
+    ],
+};
@@ -0,0 +1,9 @@
+use common::bench::CountingAllocator;
+use std::process;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() {
+    process::exit(common::runner::main(&[&day1::DAY]));
+}
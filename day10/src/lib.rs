@@ -0,0 +1,152 @@
+use common::error;
+use common::{Answer, Day, Error, Example, Input, ParseError, Part};
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::thread;
+
+pub fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+    error::parse_lines(input, |l| {
+        l.parse::<i32>()
+            .map_err(|_| ParseError::new(1, l, "an adapter joltage rating"))
+    })
+}
+
+/// Orders the adapters and adds the outlet and the device at either end.
+pub fn chain_adapters(adapters: Vec<i32>) -> Vec<i32> {
+    let mut ordered: Vec<i32> = adapters.to_vec();
+    ordered.sort_unstable();
+    ordered.insert(0, 0); // account for initial voltage
+    ordered.push(ordered[ordered.len() - 1] + 3); // account for device draw
+
+    ordered
+}
+
+fn aggregate_joltage_drop(adapters: Vec<i32>) -> HashMap<i32, i32> {
+    let differences = calculate_differences(&adapters);
+
+    let mut analysis = HashMap::new();
+
+    for i in 0..4 {
+        analysis.insert(
+            i,
+            differences.iter().copied().filter(|d| *d == i).count() as i32,
+        );
+    }
+
+    analysis
+}
+
+fn calculate_differences(adapters: &[i32]) -> Vec<i32> {
+    adapters.windows(2).map(|w| w[1] - w[0]).collect()
+}
+
+fn is_valid(adapters: &[i32]) -> bool {
+    calculate_differences(adapters)
+        .iter()
+        .copied()
+        .find(|d| *d > 3)
+        .is_none()
+}
+
+fn hash(array: &[i32]) -> String {
+    let mut out = String::new();
+
+    for n in array {
+        let _ = write!(&mut out, "{}", n);
+    }
+
+    out
+}
+
+fn discover_permutations(adapters: &[i32]) -> i32 {
+    let mut permutations: HashSet<String> = HashSet::new();
+    let mut threads = vec![];
+
+    for i in 1..adapters.len() - 2 {
+        let mut c = adapters.to_vec();
+        c.remove(i);
+        threads.push(thread::spawn(move || -> HashSet<String> {
+            let mut p: HashSet<String> = HashSet::new();
+            permutate(c, &mut p);
+            p
+        }));
+    }
+
+    for t in threads {
+        let found = t.join().unwrap();
+        for hash in found {
+            permutations.insert(hash);
+        }
+    }
+
+    permutations.len() as i32 + 1
+}
+
+fn permutate(adapters: Vec<i32>, permutations: &mut HashSet<String>) {
+    if is_valid(&adapters) {
+        let length = adapters.len();
+        let hash = hash(&adapters);
+
+        if !permutations.contains(&hash) {
+            permutations.insert(hash);
+            for i in 1..length - 2 {
+                let mut slice = adapters.clone();
+                slice.remove(i);
+                permutate(slice, permutations);
+            }
+        }
+    }
+}
+
+/// The number of 1 jolt differences multiplied by the number of 3 jolt differences.
+pub fn part1(adapters: &[i32]) -> i32 {
+    let analysis = aggregate_joltage_drop(chain_adapters(adapters.to_vec()));
+    analysis[&1] * analysis[&3]
+}
+
+/// How many distinct arrangements of the adapters still connect the outlet to the device.
+pub fn part2(adapters: &[i32]) -> i32 {
+    discover_permutations(&chain_adapters(adapters.to_vec()))
+}
+
+fn solve_part1(input: &str) -> Result<Answer, Error> {
+    Ok(part1(&parse(input)?) as Answer)
+}
+
+fn solve_part2(input: &str) -> Result<Answer, Error> {
+    Ok(part2(&parse(input)?) as Answer)
+}
+
+pub static DAY: Day = Day {
+    number: 10,
+    title: "Adapter Array",
+    part1: Some(solve_part1),
+    part2: Some(solve_part2),
+    examples: &[
+        Example {
+            part: Part::One,
+            input: Input::Example(1),
+            expected: 35,
+            solver: None,
+        },
+        Example {
+            part: Part::One,
+            input: Input::Example(2),
+            expected: 220,
+            solver: None,
+        },
+        Example {
+            part: Part::Two,
+            input: Input::Example(1),
+            expected: 8,
+            solver: None,
+        },
+        Example {
+            part: Part::Two,
+            input: Input::Example(2),
+            expected: 19208,
+            solver: None,
+        },
+    ],
+};
@@ -0,0 +1,177 @@
+use common::grid::{self, Cell, Grid};
+use common::{Answer, Day, Error, Example, Input, ParseError, Part};
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum State {
+    Floor,
+    Empty,
+    Occupied,
+}
+
+pub fn parse_row(line: &str) -> Result<Vec<State>, ParseError> {
+    line.char_indices()
+        .map(|(i, c)| match c {
+            'L' => Ok(State::Empty),
+            '.' => Ok(State::Floor),
+            '#' => Ok(State::Occupied),
+            _ => Err(ParseError::at(
+                line,
+                &line[i..i + c.len_utf8()],
+                "'L', '.' or '#'",
+            )),
+        })
+        .collect()
+}
+
+impl Cell for State {
+    const BITS: u32 = 2;
+
+    fn to_bits(self) -> u64 {
+        match self {
+            State::Floor => 0,
+            State::Empty => 1,
+            State::Occupied => 2,
+        }
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        match bits {
+            0 => State::Floor,
+            1 => State::Empty,
+            _ => State::Occupied,
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Grid<State>, ParseError> {
+    Grid::parse(input, parse_row)
+}
+
+pub fn count_adjecent_occupancy(area: &Grid<State>, x: usize, y: usize) -> usize {
+    area.neighbors(x, y)
+        .filter(|(_, _, seat)| *seat == State::Occupied)
+        .count()
+}
+
+/// Counts the first seat seen in each of the eight directions which is occupied.
+pub fn count_visible_occupancy(area: &Grid<State>, x: usize, y: usize) -> usize {
+    grid::DIRECTIONS
+        .iter()
+        .filter(|(dx, dy)| {
+            let (mut x2, mut y2) = (x as isize, y as isize);
+            loop {
+                x2 += dx;
+                y2 += dy;
+                match area.get_signed(x2, y2) {
+                    Some(State::Floor) => continue,
+                    Some(State::Occupied) => return true,
+                    _ => return false,
+                }
+            }
+        })
+        .count()
+}
+
+/// Applies one round of the seating rules from `area` into `next`, returning how many seats
+/// changed.  Occupied seats empty when more than `min_occupancy` of the seats counted are
+/// occupied.
+pub fn reseat<T>(
+    area: &Grid<State>,
+    next: &mut Grid<State>,
+    min_occupancy: usize,
+    count_occupancy: T,
+) -> usize
+where
+    T: Fn(&Grid<State>, usize, usize) -> usize,
+{
+    let mut changes = 0;
+
+    for (x, y, seat) in area.cells() {
+        let reseated = if seat == State::Floor {
+            State::Floor
+        } else {
+            let count = count_occupancy(area, x, y);
+            if count == 0 {
+                State::Occupied
+            } else if count > min_occupancy {
+                State::Empty
+            } else {
+                seat
+            }
+        };
+
+        next.set(x, y, reseated);
+        changes += if reseated != seat { 1 } else { 0 };
+    }
+
+    changes
+}
+
+pub fn print_area(area: &Grid<State>) {
+    for row in area.rows() {
+        for col in row {
+            print!(
+                "{}",
+                match col {
+                    State::Floor => ".",
+                    State::Occupied => "#",
+                    State::Empty => "L",
+                }
+            );
+        }
+        println!();
+    }
+}
+
+/// Reseats until nothing changes, swapping between two grids rather than allocating each round.
+fn settle<T>(mut area: Grid<State>, min_occupancy: usize, count_occupancy: T) -> usize
+where
+    T: Fn(&Grid<State>, usize, usize) -> usize,
+{
+    let mut next = area.clone();
+
+    while reseat(&area, &mut next, min_occupancy, &count_occupancy) > 0 {
+        std::mem::swap(&mut area, &mut next);
+    }
+
+    area.count(State::Occupied)
+}
+
+/// Occupied seats once reseating by adjacent seats settles.
+pub fn part1(area: Grid<State>) -> usize {
+    settle(area, 3, count_adjecent_occupancy)
+}
+
+/// Occupied seats once reseating by visible seats settles.
+pub fn part2(area: Grid<State>) -> usize {
+    settle(area, 4, count_visible_occupancy)
+}
+
+fn solve_part1(input: &str) -> Result<Answer, Error> {
+    Ok(part1(parse(input)?) as Answer)
+}
+
+fn solve_part2(input: &str) -> Result<Answer, Error> {
+    Ok(part2(parse(input)?) as Answer)
+}
+
+pub static DAY: Day = Day {
+    number: 11,
+    title: "Seating System",
+    part1: Some(solve_part1),
+    part2: Some(solve_part2),
+    examples: &[
+        Example {
+            part: Part::One,
+            input: Input::Example(1),
+            expected: 37,
+            solver: None,
+        },
+        Example {
+            part: Part::Two,
+            input: Input::Example(1),
+            expected: 26,
+            solver: None,
+        },
+    ],
+};
@@ -0,0 +1,330 @@
+use common::error;
+use common::{Answer, Day, Error, Example, Input, ParseError, Part};
+use std::fmt;
+use substring::Substring;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+    Left,
+    Right,
+    Forward,
+}
+
+/// A navigation instruction: a direction and how far to move or how many degrees to turn.
+#[derive(Debug)]
+pub struct Action {
+    pub direction: Direction,
+    pub amount: i32,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{0:?} for {1}", self.direction, self.amount)
+    }
+}
+
+impl Action {
+    pub fn normalize(&self) -> i32 {
+        match self.direction {
+            Direction::South | Direction::West => -self.amount,
+            _ => self.amount,
+        }
+    }
+}
+
+/// East/west and north/south offsets, east and north being positive.
+#[derive(Clone, Copy)]
+pub struct Location {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Location {
+    pub fn distance(&self, loc: Location) -> i32 {
+        (self.x - loc.x).abs() + (self.y - loc.y).abs()
+    }
+
+    pub fn add_x(&self, x: i32) -> Location {
+        Location {
+            y: self.y,
+            x: self.x + x,
+        }
+    }
+
+    pub fn add_y(&self, y: i32) -> Location {
+        Location {
+            y: self.y + y,
+            x: self.x,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+/// The ferry's heading and position, plus the waypoint relative to it for part 2.
+#[derive(Clone, Copy)]
+pub struct Ship {
+    pub bearing: Direction,
+    pub location: Location,
+    pub waypoint: Location,
+}
+
+impl Ship {
+    pub fn new(waypoint: Location) -> Self {
+        Ship {
+            bearing: Direction::East,
+            location: Location { x: 0, y: 0 },
+            waypoint,
+        }
+    }
+
+    pub fn waypoint_travel(&self, action: &Action) -> Ship {
+        match action.direction {
+            Direction::North | Direction::South => Ship {
+                waypoint: self.waypoint.add_y(action.normalize()),
+                ..*self
+            },
+            Direction::East | Direction::West => Ship {
+                waypoint: self.waypoint.add_x(action.normalize()),
+                ..*self
+            },
+            Direction::Left => self.change_bearing(action.amount),
+            Direction::Right => self.change_bearing(-action.amount),
+            Direction::Forward => Ship {
+                location: Location {
+                    x: self.location.x + self.waypoint.x * action.amount,
+                    y: self.location.y + self.waypoint.y * action.amount,
+                },
+                ..*self
+            },
+        }
+    }
+
+    pub fn direct_travel(&self, action: &Action) -> Ship {
+        match action.direction {
+            Direction::North | Direction::South => Ship {
+                location: self.location.add_y(action.normalize()),
+                ..*self
+            },
+            Direction::East | Direction::West => Ship {
+                location: self.location.add_x(action.normalize()),
+                ..*self
+            },
+            Direction::Left => Ship {
+                bearing: self.turn(action),
+                ..*self
+            },
+            Direction::Right => Ship {
+                bearing: self.turn(action),
+                ..*self
+            },
+            Direction::Forward => match self.bearing {
+                Direction::North => Ship {
+                    location: self.location.add_y(action.amount),
+                    ..*self
+                },
+                Direction::South => Ship {
+                    location: self.location.add_y(-action.amount),
+                    ..*self
+                },
+                Direction::East => Ship {
+                    location: self.location.add_x(action.amount),
+                    ..*self
+                },
+                Direction::West => Ship {
+                    location: self.location.add_x(-action.amount),
+                    ..*self
+                },
+                x => panic!(
+                    "Only cardinal directions are supported. {:?} is not a cardinal direction",
+                    x
+                ),
+            },
+        }
+    }
+
+    fn change_bearing(&self, degree: i32) -> Ship {
+        let mut ship = *self;
+
+        for _ in 0..degree.abs() / 90 {
+            ship.waypoint = if degree > 0 {
+                Location {
+                    x: -ship.waypoint.y,
+                    y: ship.waypoint.x,
+                }
+            } else {
+                Location {
+                    x: ship.waypoint.y,
+                    y: -ship.waypoint.x,
+                }
+            }
+        }
+
+        // ship.waypoint = match (360 + degree).rem_euclid(360) {
+        //     90 => Location {
+        //         x: -ship.waypoint.y,
+        //         y: ship.waypoint.x,
+        //     },
+        //     180 => Location {
+        //         x: -ship.waypoint.y,
+        //         y: -ship.waypoint.x,
+        //     },
+        //     270 => Location {
+        //         x: ship.waypoint.y,
+        //         y: -ship.waypoint.x,
+        //     },
+        //     _ => ship.waypoint,
+        // };
+
+        ship
+    }
+
+    fn turn(&self, action: &Action) -> Direction {
+        let headings = match self.bearing {
+            Direction::North => [
+                Direction::North,
+                Direction::East,
+                Direction::South,
+                Direction::West,
+            ],
+            Direction::East => [
+                Direction::East,
+                Direction::South,
+                Direction::West,
+                Direction::North,
+            ],
+            Direction::South => [
+                Direction::South,
+                Direction::West,
+                Direction::North,
+                Direction::East,
+            ],
+            Direction::West => [
+                Direction::West,
+                Direction::North,
+                Direction::East,
+                Direction::South,
+            ],
+            x => panic!(
+                "Only cardinal directions are supported. {:?} is not a cardinal direction",
+                x
+            ),
+        };
+
+        let index = (action.amount.rem_euclid(360) / 90) as usize;
+        if action.direction == Direction::Left {
+            headings[(4 - index) % 4]
+        } else if action.direction == Direction::Right {
+            headings[index]
+        } else {
+            panic!("{:?} is not a relational position change", action.direction)
+        }
+    }
+}
+
+pub fn parse_action(l: &str) -> Result<Action, ParseError> {
+    let code = l.substring(0, 1);
+    let direction = match code {
+        "N" => Direction::North,
+        "S" => Direction::South,
+        "E" => Direction::East,
+        "W" => Direction::West,
+        "L" => Direction::Left,
+        "R" => Direction::Right,
+        "F" => Direction::Forward,
+        x => return Err(ParseError::at(l, x, "one of N, S, E, W, L, R or F")),
+    };
+    let magnitude = &l[code.len()..];
+    let amount = magnitude
+        .parse::<i32>()
+        .map_err(|_| ParseError::at(l, magnitude, "a whole number magnitude"))?;
+
+    if (direction == Direction::Left || direction == Direction::Right) && amount % 90 != 0 {
+        return Err(ParseError::at(
+            l,
+            magnitude,
+            "a turn in multiples of 90 degrees",
+        ));
+    }
+
+    Ok(Action { direction, amount })
+}
+
+pub fn parse(input: &str) -> Result<Vec<Action>, ParseError> {
+    error::parse_lines(input, parse_action)
+}
+
+impl fmt::Display for Ship {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:?} at {} heading {}",
+            self.bearing, self.location, self.waypoint
+        )
+    }
+}
+
+/// Follows every leg of `journey` from `ship`, moving with `travel`.
+pub fn embark<T>(ship: Ship, journey: &[Action], travel: T) -> Ship
+where
+    T: Fn(&Ship, &Action) -> Ship,
+{
+    let mut ship2 = ship;
+
+    for leg in journey {
+        ship2 = travel(&ship2, leg);
+    }
+
+    ship2
+}
+
+/// Manhattan distance travelled steering the ship directly.
+pub fn part1(journey: &[Action]) -> i32 {
+    let initial = Ship::new(Location { x: 0, y: 0 });
+    let ship = embark(initial, journey, Ship::direct_travel);
+    initial.location.distance(ship.location)
+}
+
+/// Manhattan distance travelled steering by the waypoint.
+pub fn part2(journey: &[Action]) -> i32 {
+    let initial = Ship::new(Location { x: 10, y: 1 });
+    let ship = embark(initial, journey, Ship::waypoint_travel);
+    initial.location.distance(ship.location)
+}
+
+fn solve_part1(input: &str) -> Result<Answer, Error> {
+    Ok(part1(&parse(input)?) as Answer)
+}
+
+fn solve_part2(input: &str) -> Result<Answer, Error> {
+    Ok(part2(&parse(input)?) as Answer)
+}
+
+pub static DAY: Day = Day {
+    number: 12,
+    title: "Rain Risk",
+    part1: Some(solve_part1),
+    part2: Some(solve_part2),
+    examples: &[
+        Example {
+            part: Part::One,
+            input: Input::Example(1),
+            expected: 25,
+            solver: None,
+        },
+        Example {
+            part: Part::Two,
+            input: Input::Example(1),
+            expected: 286,
+            solver: None,
+        },
+    ],
+};
@@ -0,0 +1,227 @@
+use common::{Answer, Day, Error, Example, Input, ParseError, Part};
+use std::convert::TryFrom;
+
+/// A bus in service and its position in the schedule, which part 2 uses as its departure offset.
+#[derive(Copy, Clone, Debug)]
+pub struct Bus {
+    pub id: i64,
+    pub offset: i64,
+}
+
+pub struct Notes {
+    pub earliest: i64,
+    pub buses: Vec<Bus>,
+}
+
+/// Reads the earliest departure time and the buses in service, skipping the `x` entries but
+/// keeping every bus's offset in the list.
+pub fn parse(input: &str) -> Result<Notes, ParseError> {
+    let mut lines = input.lines();
+
+    let arrival = lines.next().unwrap_or_default();
+    let earliest = arrival
+        .parse::<i64>()
+        .map_err(|_| ParseError::new(1, arrival, "the arrival time").on_line(1))?;
+
+    let schedule = lines.next().unwrap_or_default();
+    let buses = schedule
+        .split(',')
+        .enumerate()
+        .filter(|(_, c)| *c != "x")
+        .map(|(offset, c)| match c.parse::<i64>() {
+            Ok(id) if id > 0 => Ok(Bus {
+                id,
+                offset: offset as i64,
+            }),
+            _ => Err(ParseError::at(schedule, c, "a bus id or x").on_line(2)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if buses.is_empty() {
+        return Err(ParseError::new(1, schedule, "at least one bus id").on_line(2));
+    }
+
+    Ok(Notes { earliest, buses })
+}
+
+/// The first bus to leave at or after the earliest departure and how long it is to wait for it.
+pub fn earliest_bus(notes: &Notes) -> Option<(Bus, i64)> {
+    notes
+        .buses
+        .iter()
+        .map(|b| (*b, (b.id - notes.earliest % b.id) % b.id))
+        .min_by_key(|(_, wait)| *wait)
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g`, the greatest common divisor.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// Solves the system `t ≡ remainder (mod modulus)` for each pair, returning the smallest
+/// non-negative `t` and the combined modulus.  The moduli needn't be coprime; it fails when the
+/// congruences contradict each other or the combined modulus doesn't fit in an `i128`.
+pub fn chinese_remainder(congruences: &[(i128, i128)]) -> Result<(i128, i128), Error> {
+    congruences
+        .iter()
+        .try_fold((0i128, 1i128), |(t, step), &(remainder, modulus)| {
+            let remainder = remainder.rem_euclid(modulus);
+            let (g, x, _) = extended_gcd(step, modulus);
+            let difference = remainder - t;
+            if difference % g != 0 {
+                return Err(Error::NoSolution(
+                    "no timestamp lines up every bus with its offset".to_string(),
+                ));
+            }
+
+            let overflow = || Error::NoSolution("the combined schedule overflows i128".to_string());
+            let lcm = (step / g).checked_mul(modulus).ok_or_else(overflow)?;
+            let k = (difference / g % (modulus / g))
+                .checked_mul(x)
+                .ok_or_else(overflow)?
+                .rem_euclid(modulus / g);
+            let t = step
+                .checked_mul(k)
+                .and_then(|s| s.checked_add(t))
+                .ok_or_else(overflow)?;
+            Ok((t.rem_euclid(lcm), lcm))
+        })
+}
+
+/// The earliest timestamp at which every bus departs its offset in minutes afterwards.
+pub fn contest(buses: &[Bus]) -> Result<i128, Error> {
+    let congruences = buses
+        .iter()
+        .map(|b| (-(b.offset as i128), b.id as i128))
+        .collect::<Vec<_>>();
+    chinese_remainder(&congruences).map(|(t, _)| t)
+}
+
+pub fn part1(notes: &Notes) -> Option<i64> {
+    earliest_bus(notes).map(|(bus, wait)| bus.id * wait)
+}
+
+pub fn part2(notes: &Notes) -> Result<i128, Error> {
+    contest(&notes.buses)
+}
+
+fn solve_part1(input: &str) -> Result<Answer, Error> {
+    part1(&parse(input)?).ok_or_else(|| Error::NoSolution("there are no buses".to_string()))
+}
+
+fn solve_part2(input: &str) -> Result<Answer, Error> {
+    let timestamp = part2(&parse(input)?)?;
+    Answer::try_from(timestamp)
+        .map_err(|_| Error::NoSolution(format!("{} does not fit in an answer", timestamp)))
+}
+
+pub static DAY: Day = Day {
+    number: 13,
+    title: "Shuttle Search",
+    part1: Some(solve_part1),
+    part2: Some(solve_part2),
+    examples: &[
+        Example {
+            part: Part::One,
+            input: Input::Example(1),
+            expected: 295,
+            solver: None,
+        },
+        Example {
+            part: Part::Two,
+            input: Input::Example(1),
+            expected: 1068781,
+            solver: None,
+        },
+    ],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coprime_moduli_combine_into_their_product() {
+        assert_eq!(
+            chinese_remainder(&[(2, 3), (3, 5), (2, 7)]).unwrap(),
+            (23, 105)
+        );
+    }
+
+    #[test]
+    fn shared_factors_combine_into_the_lcm() {
+        assert_eq!(chinese_remainder(&[(0, 4), (2, 6)]).unwrap(), (8, 12));
+        assert_eq!(chinese_remainder(&[(3, 6), (3, 6)]).unwrap(), (3, 6));
+    }
+
+    #[test]
+    fn contradictions_have_no_solution() {
+        assert!(chinese_remainder(&[(0, 4), (1, 6)]).is_err());
+        assert!(solve_part2("0\n4,6").is_err());
+    }
+
+    #[test]
+    fn negative_remainders_wrap() {
+        assert_eq!(chinese_remainder(&[(-1, 5)]).unwrap(), (4, 5));
+        assert_eq!(chinese_remainder(&[]).unwrap(), (0, 1));
+    }
+
+    #[test]
+    fn large_moduli_stay_within_i128() {
+        // Three primes near 10^12, so the combined modulus is around 10^36.
+        let moduli = [999_999_999_989i128, 999_999_999_961, 999_999_999_959];
+        let congruences = moduli
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (-(i as i128), *m))
+            .collect::<Vec<_>>();
+        let (t, lcm) = chinese_remainder(&congruences).unwrap();
+        assert_eq!(lcm, moduli.iter().product::<i128>());
+        for (remainder, modulus) in congruences {
+            assert_eq!(t.rem_euclid(modulus), remainder.rem_euclid(modulus));
+        }
+    }
+
+    #[test]
+    fn moduli_beyond_i128_are_reported() {
+        // Four primes near 10^12 multiply to around 10^48.
+        let congruences = [
+            (0, 999_999_999_989),
+            (-1, 999_999_999_961),
+            (-2, 999_999_999_959),
+            (-3, 999_999_999_947),
+        ];
+        assert!(matches!(
+            chinese_remainder(&congruences),
+            Err(Error::NoSolution(_))
+        ));
+        let input = "0\n999999999989,999999999961,999999999959,999999999947";
+        assert!(matches!(solve_part2(input), Err(Error::NoSolution(_))));
+    }
+
+    #[test]
+    fn timestamps_beyond_an_answer_are_reported() {
+        let input = "0\n999999999989,999999999961,999999999959";
+        assert!(matches!(solve_part2(input), Err(Error::NoSolution(_))));
+    }
+
+    #[test]
+    fn the_example_schedules_line_up() {
+        let examples: [(&str, i128); 5] = [
+            ("17,x,13,19", 3417),
+            ("67,7,59,61", 754018),
+            ("67,x,7,59,61", 779210),
+            ("67,7,x,59,61", 1261476),
+            ("1789,37,47,1889", 1202161486),
+        ];
+        for (schedule, expected) in examples.iter() {
+            let notes = parse(&format!("0\n{}", schedule)).unwrap();
+            assert_eq!(part2(&notes).unwrap(), *expected, "{}", schedule);
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use common::input;
+use common::report::{self, Format};
+use common::runner::{self, CommandOptions};
+use day2::policy::{self, PasswordPolicy};
+use day2::semantics::Semantics;
+use day2::stream;
+use day2::violations::{self, Violation};
+use day2::Credential;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+
+/// The subcommands, as the runner's `--help` lists them.
+pub const COMMANDS: &str = "    policies        count the entries each policy accepts
+    report          list every entry a policy rejects and why";
+
+const USAGE: &str = "Usage: day2 policies|report [POLICY...] [--semantics bytes|scalars|graphemes]
+                   [--csv PATH] [--stream] [--threads N] [OPTIONS]
+
+    policies        count the entries each policy accepts
+    report          list every entry a policy rejects and why
+    POLICY          count, xor, and, forbidden or regex:PATTERN, defaults to count and xor
+    --semantics     whether letters and positions are bytes, Unicode scalar values or
+                    grapheme clusters, defaults to scalars
+    --csv           also write the report's violations to PATH as CSV, - for stdout
+    --stream        count policies while reading rather than loading the whole database
+    --threads       check streamed entries on N threads, implies --stream";
+
+#[derive(Eq, PartialEq)]
+enum Command {
+    Policies,
+    Report,
+}
+
+struct Options {
+    command: Command,
+    policies: Vec<Box<dyn PasswordPolicy>>,
+    semantics: Semantics,
+    csv: Option<PathBuf>,
+    stream: bool,
+    threads: usize,
+}
+
+#[derive(Serialize)]
+struct PolicyResult {
+    policy: String,
+    accepted: usize,
+    rejected: usize,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut args = args.iter();
+    let mut options = Options {
+        command: match args.next().map(|a| a.as_str()) {
+            Some("policies") => Command::Policies,
+            Some("report") => Command::Report,
+            x => return Err(format!("Unknown command {}", x.unwrap_or_default())),
+        },
+        policies: vec![],
+        semantics: Semantics::default(),
+        csv: None,
+        stream: false,
+        threads: 1,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--semantics" | "-s" => {
+                let value = args.next().ok_or("--semantics requires a value")?;
+                options.semantics = Semantics::parse(value)?;
+            }
+            "--csv" => {
+                let value = args.next().ok_or("--csv requires a value")?;
+                options.csv = Some(PathBuf::from(value));
+            }
+            "--stream" => options.stream = true,
+            "--threads" | "-t" => {
+                let value = args.next().ok_or("--threads requires a value")?;
+                options.threads = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| format!("{} is not a positive number of threads", value))?;
+                options.stream = true;
+            }
+            x if x.starts_with('-') => return Err(format!("Unknown option {}", x)),
+            x => options.policies.push(policy::from_name(x)?),
+        }
+    }
+
+    if options.policies.is_empty() {
+        options.policies = vec![policy::from_name("count")?, policy::from_name("xor")?];
+    }
+
+    if options.csv.is_some() && options.command != Command::Report {
+        return Err("--csv can only be used with report".to_string());
+    }
+
+    if options.stream && options.command != Command::Policies {
+        return Err("--stream and --threads can only be used with policies".to_string());
+    }
+
+    Ok(options)
+}
+
+fn print_counts(entries: usize, counts: Vec<usize>, options: &Options, format: Format) {
+    for (policy, accepted) in options.policies.iter().zip(counts) {
+        let result = PolicyResult {
+            policy: policy.name(),
+            accepted,
+            rejected: entries - accepted,
+        };
+        match format {
+            Format::Text => println!(
+                "{0}\t{1} accepted\t{2} rejected",
+                result.policy, result.accepted, result.rejected
+            ),
+            Format::Json => report::print_json(&result),
+        }
+    }
+}
+
+fn export_csv(path: &Path, violations: &[Violation]) -> Result<(), String> {
+    let result = if input::is_stdin(path) {
+        violations::write_csv(io::stdout(), violations)
+    } else {
+        File::create(path)
+            .map_err(csv::Error::from)
+            .and_then(|file| violations::write_csv(file, violations))
+    };
+    result.map_err(|e| format!("Unable to write the report {}: {}", path.display(), e))
+}
+
+fn print_violations(
+    credentials: &[Credential],
+    options: &Options,
+    format: Format,
+) -> Result<(), String> {
+    let violations = violations::violations(credentials, &options.policies);
+
+    // CSV written to stdout replaces the usual listing rather than interleaving with it.
+    if !options.csv.as_deref().is_some_and(input::is_stdin) {
+        for violation in violations.iter() {
+            match format {
+                Format::Text => println!(
+                    "line {0}: {1} {2}\trejected by {3}: {4} ({5})",
+                    violation.line,
+                    violation.rule,
+                    violation.password,
+                    violation.policy,
+                    violation.reason,
+                    violation.observed
+                ),
+                Format::Json => report::print_json(violation),
+            }
+        }
+    }
+
+    match &options.csv {
+        Some(path) => export_csv(path, &violations),
+        None => Ok(()),
+    }
+}
+
+fn stream_counts(path: &Path, options: &Options, format: Format) -> Result<(), common::Error> {
+    let source = input::source(path);
+    let tally = if input::is_stdin(path) {
+        let stdin = io::stdin();
+        let reader = stdin.lock();
+        stream::tally(
+            reader,
+            source,
+            &options.policies,
+            options.semantics,
+            options.threads,
+        )?
+    } else {
+        let file = File::open(path).map_err(|source| common::Error::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        stream::tally(
+            BufReader::new(file),
+            source,
+            &options.policies,
+            options.semantics,
+            options.threads,
+        )?
+    };
+    print_counts(tally.entries, tally.accepted, options, format);
+    Ok(())
+}
+
+fn run(options: Options, shared: &CommandOptions) -> Result<(), String> {
+    if options.stream {
+        return stream_counts(&shared.path(&day2::DAY), &options, shared.format)
+            .map_err(|e| e.to_string());
+    }
+    let credentials = shared
+        .load(&day2::DAY, |contents| {
+            day2::parse_with(contents, options.semantics)
+        })
+        .map_err(|e| e.to_string())?;
+
+    match options.command {
+        Command::Policies => {
+            print_counts(
+                credentials.len(),
+                policy::evaluate(&credentials, &options.policies),
+                &options,
+                shared.format,
+            );
+            Ok(())
+        }
+        Command::Report => print_violations(&credentials, &options, shared.format),
+    }
+}
+
+/// Evaluates the password database against the chosen policies.
+pub fn main(args: &[String]) -> i32 {
+    runner::command(&day2::DAY, USAGE, args, parse_args, run)
+}
@@ -0,0 +1,148 @@
+use common::error;
+use common::{Answer, Day, Error, Example, Input, ParseError, Part};
+use policy::{CountRange, PasswordPolicy, PositionalXor};
+use semantics::Semantics;
+
+pub mod policy;
+pub mod semantics;
+pub mod stream;
+pub mod violations;
+
+/// A line of the password database: the policy numbers, its letter and the password.  The
+/// letter is a single character under the semantics the entry was parsed with.
+pub struct Credential {
+    pub min: usize,
+    pub max: usize,
+    pub token: String,
+    pub password: String,
+    pub semantics: Semantics,
+}
+
+impl Credential {
+    /// How many times the letter appears in the password.
+    pub fn occurrences(&self) -> usize {
+        let token = self.token.as_bytes();
+        self.semantics
+            .units(&self.password)
+            .filter(|u| *u == token)
+            .count()
+    }
+
+    /// The bytes of the character at the 1-based `position` of the password.
+    pub fn unit(&self, position: usize) -> Option<&[u8]> {
+        position
+            .checked_sub(1)
+            .and_then(|i| self.semantics.units(&self.password).nth(i))
+    }
+
+    /// The character at the 1-based `position` for display.  Under byte semantics this may be
+    /// part of a multi-byte character, which is shown as a replacement character.
+    pub fn at(&self, position: usize) -> Option<String> {
+        self.unit(position)
+            .map(|u| String::from_utf8_lossy(u).into_owned())
+    }
+
+    /// Whether the 1-based `position` of the password holds the letter.
+    pub fn holds_token(&self, position: usize) -> bool {
+        self.unit(position) == Some(self.token.as_bytes())
+    }
+
+    /// The entry's rule as written in the database, e.g. `1-3 a`.
+    pub fn rule(&self) -> String {
+        format!("{}-{} {}", self.min, self.max, self.token)
+    }
+}
+
+pub fn parse_record(input: &str) -> Result<Credential, ParseError> {
+    parse_record_with(input, Semantics::default())
+}
+
+pub fn parse_record_with(input: &str, semantics: Semantics) -> Result<Credential, ParseError> {
+    let mut iter = input.split_whitespace();
+    let range = iter
+        .next()
+        .ok_or_else(|| ParseError::new(1, input, "a range such as 1-3"))?;
+    let (min, max) = match range.split_once('-') {
+        Some((min, max)) => (
+            min.parse::<usize>()
+                .map_err(|_| ParseError::at(input, min, "a minimum count"))?,
+            max.parse::<usize>()
+                .map_err(|_| ParseError::at(input, max, "a maximum count"))?,
+        ),
+        None => return Err(ParseError::at(input, range, "a range such as 1-3")),
+    };
+    let rule = iter
+        .next()
+        .ok_or_else(|| ParseError::new(input.len() + 1, "", "a letter such as a:"))?;
+    let token = match rule.strip_suffix(':') {
+        Some(token) if semantics.len(token) == 1 => token.to_string(),
+        _ => return Err(ParseError::at(input, rule, "a letter such as a:")),
+    };
+    let password = iter
+        .next()
+        .ok_or_else(|| ParseError::new(input.len() + 1, "", "a password"))?
+        .to_string();
+    if min == 0 || max < min {
+        return Err(ParseError::at(
+            input,
+            range,
+            "a range starting at 1 or more and not ending before it starts",
+        ));
+    }
+
+    Ok(Credential {
+        min,
+        max,
+        token,
+        password,
+        semantics,
+    })
+}
+
+pub fn parse(input: &str) -> Result<Vec<Credential>, ParseError> {
+    parse_with(input, Semantics::default())
+}
+
+pub fn parse_with(input: &str, semantics: Semantics) -> Result<Vec<Credential>, ParseError> {
+    error::parse_lines(input, |l| parse_record_with(l, semantics))
+}
+
+pub fn part1(credentials: &[Credential]) -> usize {
+    credentials.iter().filter(|c| CountRange.accepts(c)).count()
+}
+
+pub fn part2(credentials: &[Credential]) -> usize {
+    credentials
+        .iter()
+        .filter(|c| PositionalXor.accepts(c))
+        .count()
+}
+
+fn solve_part1(input: &str) -> Result<Answer, Error> {
+    Ok(part1(&parse(input)?) as Answer)
+}
+
+fn solve_part2(input: &str) -> Result<Answer, Error> {
+    Ok(part2(&parse(input)?) as Answer)
+}
+
+pub static DAY: Day = Day {
+    number: 2,
+    title: "Password Philosophy",
+    part1: Some(solve_part1),
+    part2: Some(solve_part2),
+    examples: &[
+        Example {
+            part: Part::One,
+            input: Input::Example(1),
+            expected: 2,
+            solver: None,
+        },
+        Example {
+            part: Part::Two,
+            input: Input::Example(1),
+            expected: 1,
+            solver: None,
+        },
+    ],
+};
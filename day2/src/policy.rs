@@ -0,0 +1,196 @@
+use crate::Credential;
+use regex::Regex;
+
+/// A rule a password database entry can be checked against.
+pub trait PasswordPolicy: Send + Sync {
+    /// How the policy is named on the command line and in reports.
+    fn name(&self) -> String;
+    fn accepts(&self, credential: &Credential) -> bool;
+    /// What the policy looked at in the password, e.g. the letter count.
+    fn observe(&self, credential: &Credential) -> String;
+    /// Why the policy rejects `credential`.
+    fn reason(&self, credential: &Credential) -> String;
+}
+
+fn occurrences(credential: &Credential) -> String {
+    format!("{} occurrences", credential.occurrences())
+}
+
+fn positions(credential: &Credential) -> String {
+    let at = |position: usize| match credential.at(position) {
+        Some(c) => format!("{:?}", c),
+        None => "none".to_string(),
+    };
+    format!(
+        "{}={} {}={}",
+        credential.min,
+        at(credential.min),
+        credential.max,
+        at(credential.max)
+    )
+}
+
+/// The sled rental policy: the letter appears between `min` and `max` times.
+pub struct CountRange;
+
+impl PasswordPolicy for CountRange {
+    fn name(&self) -> String {
+        "count".to_string()
+    }
+
+    fn accepts(&self, credential: &Credential) -> bool {
+        (credential.min..=credential.max).contains(&credential.occurrences())
+    }
+
+    fn observe(&self, credential: &Credential) -> String {
+        occurrences(credential)
+    }
+
+    fn reason(&self, credential: &Credential) -> String {
+        format!(
+            "{} appears {} times, outside {}-{}",
+            credential.token,
+            credential.occurrences(),
+            credential.min,
+            credential.max
+        )
+    }
+}
+
+/// The toboggan policy: exactly one of positions `min` and `max` (1-based) holds the letter.
+pub struct PositionalXor;
+
+impl PasswordPolicy for PositionalXor {
+    fn name(&self) -> String {
+        "xor".to_string()
+    }
+
+    fn accepts(&self, credential: &Credential) -> bool {
+        credential.holds_token(credential.min) != credential.holds_token(credential.max)
+    }
+
+    fn observe(&self, credential: &Credential) -> String {
+        positions(credential)
+    }
+
+    fn reason(&self, credential: &Credential) -> String {
+        match credential.holds_token(credential.min) {
+            true => format!("both positions hold {}", credential.token),
+            false => format!("neither position holds {}", credential.token),
+        }
+    }
+}
+
+/// Both positions `min` and `max` hold the letter.
+pub struct PositionalAnd;
+
+impl PasswordPolicy for PositionalAnd {
+    fn name(&self) -> String {
+        "and".to_string()
+    }
+
+    fn accepts(&self, credential: &Credential) -> bool {
+        credential.holds_token(credential.min) && credential.holds_token(credential.max)
+    }
+
+    fn observe(&self, credential: &Credential) -> String {
+        positions(credential)
+    }
+
+    fn reason(&self, credential: &Credential) -> String {
+        let missing = [credential.min, credential.max]
+            .iter()
+            .filter(|p| !credential.holds_token(**p))
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>();
+        match missing.len() {
+            1 => format!("position {} does not hold {}", missing[0], credential.token),
+            _ => format!(
+                "positions {} do not hold {}",
+                missing.join(" and "),
+                credential.token
+            ),
+        }
+    }
+}
+
+/// The letter doesn't appear in the password at all.
+pub struct ForbiddenCharacter;
+
+impl PasswordPolicy for ForbiddenCharacter {
+    fn name(&self) -> String {
+        "forbidden".to_string()
+    }
+
+    fn accepts(&self, credential: &Credential) -> bool {
+        credential.occurrences() == 0
+    }
+
+    fn observe(&self, credential: &Credential) -> String {
+        occurrences(credential)
+    }
+
+    fn reason(&self, credential: &Credential) -> String {
+        format!(
+            "{} appears {} times",
+            credential.token,
+            credential.occurrences()
+        )
+    }
+}
+
+/// The password matches a regular expression, ignoring the entry's own rule.
+pub struct Pattern {
+    regex: Regex,
+}
+
+impl Pattern {
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Pattern {
+            regex: Regex::new(pattern)?,
+        })
+    }
+}
+
+impl PasswordPolicy for Pattern {
+    fn name(&self) -> String {
+        format!("regex:{}", self.regex.as_str())
+    }
+
+    fn accepts(&self, credential: &Credential) -> bool {
+        self.regex.is_match(&credential.password)
+    }
+
+    fn observe(&self, credential: &Credential) -> String {
+        credential.password.clone()
+    }
+
+    fn reason(&self, _: &Credential) -> String {
+        format!("does not match {}", self.regex.as_str())
+    }
+}
+
+/// Builds a policy from its command line name: `count`, `xor`, `and`, `forbidden` or
+/// `regex:PATTERN`.
+pub fn from_name(name: &str) -> Result<Box<dyn PasswordPolicy>, String> {
+    match name {
+        "count" => Ok(Box::new(CountRange)),
+        "xor" => Ok(Box::new(PositionalXor)),
+        "and" => Ok(Box::new(PositionalAnd)),
+        "forbidden" => Ok(Box::new(ForbiddenCharacter)),
+        x => match x.strip_prefix("regex:") {
+            Some(pattern) => Pattern::new(pattern)
+                .map(|p| Box::new(p) as Box<dyn PasswordPolicy>)
+                .map_err(|e| format!("Invalid pattern {}: {}", pattern, e)),
+            None => Err(format!("Unknown policy {}", x)),
+        },
+    }
+}
+
+/// How many entries each policy accepts, in the order the policies were given.
+pub fn evaluate(credentials: &[Credential], policies: &[Box<dyn PasswordPolicy>]) -> Vec<usize> {
+    policies
+        .iter()
+        .map(|p| credentials.iter().filter(|c| p.accepts(c)).count())
+        .collect()
+}
@@ -0,0 +1,43 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// What counts as one character when counting letters and indexing positions in a password.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Semantics {
+    /// Each UTF-8 byte is a character, so `é` occupies two positions.
+    Bytes,
+    /// Each Unicode scalar value is a character.  A combining accent is its own character.
+    #[default]
+    Scalars,
+    /// Each extended grapheme cluster is a character, matching what a reader would count.
+    Graphemes,
+}
+
+impl Semantics {
+    pub fn parse(value: &str) -> Result<Semantics, String> {
+        match value {
+            "bytes" => Ok(Semantics::Bytes),
+            "scalars" => Ok(Semantics::Scalars),
+            "graphemes" => Ok(Semantics::Graphemes),
+            x => Err(format!("Unknown semantics {}", x)),
+        }
+    }
+
+    /// Splits `text` into characters, each given as the bytes it's encoded with.
+    pub fn units<'a>(self, text: &'a str) -> Box<dyn Iterator<Item = &'a [u8]> + 'a> {
+        match self {
+            Semantics::Bytes => Box::new(text.as_bytes().chunks(1)),
+            Semantics::Scalars => Box::new(
+                text.char_indices()
+                    .map(move |(i, c)| &text.as_bytes()[i..i + c.len_utf8()]),
+            ),
+            Semantics::Graphemes => Box::new(text.graphemes(true).map(|g| g.as_bytes())),
+        }
+    }
+
+    pub fn len(self, text: &str) -> usize {
+        match self {
+            Semantics::Bytes => text.len(),
+            _ => self.units(text).count(),
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use crate::parse_record_with;
+use crate::policy::PasswordPolicy;
+use crate::semantics::Semantics;
+use common::{Error, ParseError};
+use std::io::BufRead;
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Lines handed to a worker at a time when evaluating across threads.
+const BATCH: usize = 4096;
+
+/// Running totals for a password database: how many entries were read and how many of them each
+/// policy accepted, in the order the policies were given.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Tally {
+    pub entries: usize,
+    pub accepted: Vec<usize>,
+}
+
+impl Tally {
+    fn new(policies: usize) -> Self {
+        Tally {
+            entries: 0,
+            accepted: vec![0; policies],
+        }
+    }
+
+    fn add(
+        &mut self,
+        line: &str,
+        policies: &[Box<dyn PasswordPolicy>],
+        semantics: Semantics,
+    ) -> Result<(), ParseError> {
+        let credential = parse_record_with(line, semantics)?;
+        self.entries += 1;
+        for (count, policy) in self.accepted.iter_mut().zip(policies) {
+            if policy.accepts(&credential) {
+                *count += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn merge(&mut self, other: Tally) {
+        self.entries += other.entries;
+        for (count, more) in self.accepted.iter_mut().zip(other.accepted) {
+            *count += more;
+        }
+    }
+}
+
+/// Reads lines into `buffer`, returning `false` at the end of the input.
+fn next_line<R: BufRead>(reader: &mut R, buffer: &mut String, path: &Path) -> Result<bool, Error> {
+    buffer.clear();
+    let read = reader.read_line(buffer).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let trimmed = buffer.trim_end_matches(&['\r', '\n'][..]).len();
+    buffer.truncate(trimmed);
+    Ok(read > 0)
+}
+
+/// Evaluates every entry read from `reader` against `policies` without keeping the entries,
+/// so memory use doesn't grow with the input.  With more than one thread, batches of lines are
+/// parsed and checked in parallel while this thread reads ahead.  `path` only labels errors.
+pub fn tally<R: BufRead>(
+    mut reader: R,
+    path: &Path,
+    policies: &[Box<dyn PasswordPolicy>],
+    semantics: Semantics,
+    threads: usize,
+) -> Result<Tally, Error> {
+    if threads <= 1 {
+        let mut tally = Tally::new(policies.len());
+        let mut line = String::new();
+        let mut number = 0;
+        while next_line(&mut reader, &mut line, path)? {
+            number += 1;
+            tally
+                .add(&line, policies, semantics)
+                .map_err(|e| Error::from(e.on_line(number)).in_file(path))?;
+        }
+        return Ok(tally);
+    }
+
+    // Batches carry the number of their first line so workers can report errors in place.  Only
+    // the workers hold the receiver, so it closes once the last of them stops.
+    let (sender, receiver) = mpsc::sync_channel::<(usize, Vec<String>)>(threads * 2);
+    let receiver = Arc::new(Mutex::new(receiver));
+
+    thread::scope(|scope| {
+        let workers = (0..threads)
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                scope.spawn(move || -> Result<Tally, ParseError> {
+                    let mut tally = Tally::new(policies.len());
+                    loop {
+                        let batch = receiver.lock().ok().and_then(|r| r.recv().ok());
+                        let (first, lines) = match batch {
+                            Some(batch) => batch,
+                            None => return Ok(tally),
+                        };
+                        for (i, line) in lines.iter().enumerate() {
+                            tally
+                                .add(line, policies, semantics)
+                                .map_err(|e| e.on_line(first + i))?;
+                        }
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        drop(receiver);
+
+        let mut read = Ok(());
+        let mut number = 1;
+        let mut line = String::new();
+        'reading: loop {
+            let mut batch = Vec::with_capacity(BATCH);
+            while batch.len() < BATCH {
+                match next_line(&mut reader, &mut line, path) {
+                    Ok(true) => batch.push(line.clone()),
+                    Ok(false) => break,
+                    Err(e) => {
+                        read = Err(e);
+                        break 'reading;
+                    }
+                }
+            }
+            if batch.is_empty() {
+                break;
+            }
+            let size = batch.len();
+            // A closed channel means every worker has stopped on a parse error.
+            if sender.send((number, batch)).is_err() {
+                break;
+            }
+            number += size;
+        }
+        drop(sender);
+
+        let mut total = Tally::new(policies.len());
+        let mut first_error: Option<ParseError> = None;
+        for worker in workers {
+            match worker.join().expect("a policy worker panicked") {
+                Ok(tally) => total.merge(tally),
+                Err(e) => {
+                    if first_error.as_ref().is_none_or(|f| e.line < f.line) {
+                        first_error = Some(e);
+                    }
+                }
+            }
+        }
+
+        read?;
+        match first_error {
+            Some(e) => Err(Error::from(e).in_file(path)),
+            None => Ok(total),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::{CountRange, PositionalXor};
+    use crate::{parse, part1, part2};
+
+    fn policies() -> Vec<Box<dyn PasswordPolicy>> {
+        vec![Box::new(CountRange), Box::new(PositionalXor)]
+    }
+
+    /// A database spanning several batches, with a mix of entries each policy accepts.
+    fn database(entries: usize) -> String {
+        let samples = [
+            "1-3 a: abcde",
+            "1-3 b: cdefg",
+            "2-9 c: ccccccccc",
+            "1-12 a: aaa",
+        ];
+        (0..entries)
+            .map(|i| samples[i % samples.len()])
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn run(input: &str, threads: usize) -> Result<Tally, Error> {
+        tally(
+            input.as_bytes(),
+            Path::new("test.txt"),
+            &policies(),
+            Semantics::default(),
+            threads,
+        )
+    }
+
+    #[test]
+    fn every_thread_count_agrees_with_the_parts() {
+        let input = database(BATCH * 3 + 17);
+        let credentials = parse(&input).unwrap();
+        let expected = Tally {
+            entries: credentials.len(),
+            accepted: vec![part1(&credentials), part2(&credentials)],
+        };
+        for threads in [1, 2, 3, 8].iter() {
+            assert_eq!(
+                run(&input, *threads).unwrap(),
+                expected,
+                "{} threads",
+                threads
+            );
+        }
+    }
+
+    #[test]
+    fn batch_boundaries_keep_every_line() {
+        for entries in [BATCH - 1, BATCH, BATCH + 1, BATCH * 2].iter() {
+            let input = database(*entries);
+            assert_eq!(run(&input, 4).unwrap().entries, *entries);
+            assert_eq!(run(&format!("{}\n", input), 4).unwrap().entries, *entries);
+        }
+    }
+
+    #[test]
+    fn crlf_line_endings_are_trimmed() {
+        let input = database(10).replace('\n', "\r\n");
+        assert_eq!(run(&input, 1).unwrap(), run(&database(10), 1).unwrap());
+        assert_eq!(run(&input, 2).unwrap(), run(&database(10), 1).unwrap());
+    }
+
+    #[test]
+    fn errors_report_the_first_bad_line() {
+        let mut lines = database(BATCH * 3)
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        // One bad line on each side of a batch boundary; the earlier must win.
+        lines[BATCH * 2 + 5] = "nonsense".to_string();
+        lines[BATCH] = "also nonsense".to_string();
+        let input = lines.join("\n");
+        for threads in [1, 4].iter() {
+            match run(&input, *threads) {
+                Err(Error::Parse(e)) => {
+                    assert_eq!(e.line, BATCH + 1, "{} threads", threads);
+                    assert_eq!(e.file.as_deref(), Some(Path::new("test.txt")));
+                }
+                other => panic!("{} threads: {:?}", threads, other),
+            }
+        }
+    }
+
+    #[test]
+    fn stops_once_every_worker_fails() {
+        // A bad line opening every batch stops each worker on the first batch it takes, long
+        // before the reader has queued the rest.
+        let batches = 16;
+        let mut lines = database(BATCH * batches)
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        for batch in 0..batches {
+            lines[batch * BATCH] = "nonsense".to_string();
+        }
+        let input = lines.join("\n");
+        for threads in [2, 4].iter() {
+            match run(&input, *threads) {
+                Err(Error::Parse(e)) => assert_eq!(e.line, 1, "{} threads", threads),
+                other => panic!("{} threads: {:?}", threads, other),
+            }
+        }
+    }
+
+    #[test]
+    fn merging_adds_each_count() {
+        let mut tally = Tally {
+            entries: 3,
+            accepted: vec![1, 2],
+        };
+        tally.merge(Tally {
+            entries: 4,
+            accepted: vec![3, 0],
+        });
+        assert_eq!(
+            tally,
+            Tally {
+                entries: 7,
+                accepted: vec![4, 2],
+            }
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use crate::policy::PasswordPolicy;
+use crate::Credential;
+use serde::Serialize;
+use std::io;
+
+/// An entry of the password database which a policy rejected.
+#[derive(Debug, Serialize)]
+pub struct Violation {
+    pub line: usize,
+    pub policy: String,
+    pub rule: String,
+    pub password: String,
+    pub observed: String,
+    pub reason: String,
+}
+
+impl Violation {
+    fn new(line: usize, policy: &dyn PasswordPolicy, credential: &Credential) -> Self {
+        Violation {
+            line,
+            policy: policy.name(),
+            rule: credential.rule(),
+            password: credential.password.clone(),
+            observed: policy.observe(credential),
+            reason: policy.reason(credential),
+        }
+    }
+}
+
+/// Every entry each policy rejects, in file order.  `credentials` must hold one entry per line
+/// as `parse` returns them so the line numbers line up.
+pub fn violations(
+    credentials: &[Credential],
+    policies: &[Box<dyn PasswordPolicy>],
+) -> Vec<Violation> {
+    credentials
+        .iter()
+        .enumerate()
+        .flat_map(|(i, credential)| {
+            policies
+                .iter()
+                .filter(move |p| !p.accepts(credential))
+                .map(move |p| Violation::new(i + 1, p.as_ref(), credential))
+        })
+        .collect()
+}
+
+pub fn write_csv<W: io::Write>(writer: W, violations: &[Violation]) -> Result<(), csv::Error> {
+    let mut writer = csv::Writer::from_writer(writer);
+    for violation in violations {
+        writer.serialize(violation)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use day2::policy::{CountRange, PasswordPolicy, PositionalAnd, PositionalXor};
+use day2::semantics::Semantics;
+use day2::{parse_record_with, parse_with, part1, part2};
+
+const ALL: [Semantics; 3] = [Semantics::Bytes, Semantics::Scalars, Semantics::Graphemes];
+
+// "é" as a single precomposed scalar and as "e" followed by a combining acute accent.
+const PRECOMPOSED: &str = "\u{e9}";
+const DECOMPOSED: &str = "e\u{301}";
+
+#[test]
+fn ascii_databases_agree_under_every_semantics() {
+    let example = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n";
+    for semantics in ALL.iter() {
+        let credentials = parse_with(example, *semantics).unwrap();
+        assert_eq!(part1(&credentials), 2, "{:?}", semantics);
+        assert_eq!(part2(&credentials), 1, "{:?}", semantics);
+    }
+}
+
+#[test]
+fn bytes_index_inside_multibyte_characters() {
+    // h, then the two bytes of é, then l at bytes 4 and 5.
+    let line = format!("4-5 l: h{}llo", PRECOMPOSED);
+    let bytes = parse_record_with(&line, Semantics::Bytes).unwrap();
+    assert!(PositionalAnd.accepts(&bytes));
+    assert!(!PositionalXor.accepts(&bytes));
+
+    // As scalars the l's sit at 3 and 4, so position 5 is the o.
+    let scalars = parse_record_with(&line, Semantics::Scalars).unwrap();
+    assert!(!PositionalAnd.accepts(&scalars));
+    assert!(PositionalXor.accepts(&scalars));
+}
+
+#[test]
+fn bytes_reject_multibyte_letters() {
+    let line = format!("1-2 {}: h{}llo", PRECOMPOSED, PRECOMPOSED);
+    assert!(parse_record_with(&line, Semantics::Bytes).is_err());
+    assert!(parse_record_with(&line, Semantics::Scalars).is_ok());
+}
+
+#[test]
+fn positions_past_the_end_hold_nothing() {
+    // Six bytes but five scalars, so position 6 is the o only when counting bytes.
+    let line = format!("1-6 o: h{}llo", PRECOMPOSED);
+    let bytes = parse_record_with(&line, Semantics::Bytes).unwrap();
+    assert!(PositionalXor.accepts(&bytes));
+    for semantics in [Semantics::Scalars, Semantics::Graphemes].iter() {
+        let credential = parse_record_with(&line, *semantics).unwrap();
+        assert_eq!(credential.at(6), None);
+        assert!(!PositionalXor.accepts(&credential));
+    }
+}
+
+#[test]
+fn counts_may_exceed_the_password_length() {
+    let credentials = parse_with("1-12 a: aaa\n", Semantics::default()).unwrap();
+    assert_eq!(part1(&credentials), 1);
+    assert_eq!(part2(&credentials), 1);
+}
+
+#[test]
+fn precomposed_letters_are_one_scalar() {
+    let line = format!("2-3 {0}: h{0}{0}lo", PRECOMPOSED);
+    for semantics in [Semantics::Scalars, Semantics::Graphemes].iter() {
+        let credential = parse_record_with(&line, *semantics).unwrap();
+        assert_eq!(credential.occurrences(), 2);
+        assert!(PositionalAnd.accepts(&credential));
+        assert_eq!(credential.at(2).as_deref(), Some(PRECOMPOSED));
+    }
+}
+
+#[test]
+fn combining_accents_are_one_grapheme() {
+    let line = format!("1-2 {0}: h{0}llo", DECOMPOSED);
+    assert!(parse_record_with(&line, Semantics::Scalars).is_err());
+
+    let credential = parse_record_with(&line, Semantics::Graphemes).unwrap();
+    assert_eq!(credential.occurrences(), 1);
+    assert!(CountRange.accepts(&credential));
+    assert!(credential.holds_token(2));
+    assert_eq!(credential.at(3).as_deref(), Some("l"));
+}
+
+#[test]
+fn combining_accents_do_not_match_the_bare_letter_as_graphemes() {
+    // Counting scalars finds the "e" under the accent, graphemes only see "é".
+    let line = format!("1-1 e: h{}llo", DECOMPOSED);
+    let scalars = parse_record_with(&line, Semantics::Scalars).unwrap();
+    assert_eq!(scalars.occurrences(), 1);
+
+    let graphemes = parse_record_with(&line, Semantics::Graphemes).unwrap();
+    assert_eq!(graphemes.occurrences(), 0);
+    assert!(!CountRange.accepts(&graphemes));
+}
+
+#[test]
+fn emoji_sequences_are_one_grapheme() {
+    let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+    let line = format!("1-2 {0}: {0}x", family);
+    assert!(parse_record_with(&line, Semantics::Scalars).is_err());
+
+    let credential = parse_record_with(&line, Semantics::Graphemes).unwrap();
+    assert!(PositionalXor.accepts(&credential));
+    assert_eq!(Semantics::Graphemes.len(&credential.password), 2);
+    assert_eq!(Semantics::Scalars.len(&credential.password), 6);
+    assert_eq!(Semantics::Bytes.len(&credential.password), 19);
+}
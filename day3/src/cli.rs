@@ -0,0 +1,214 @@
+use common::grid::Grid;
+use common::input;
+use common::report::{self, Format};
+use common::runner::{self, CommandOptions};
+use day3::render;
+use day3::search::{self, Ranking, Trial};
+use day3::topology::Topology;
+use day3::Movement;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+/// The subcommands, as the runner's `--help` lists them.
+pub const COMMANDS: &str =
+    "    search          rank every slope in a range by the trees it runs into
+    render          draw the map with the cells a slope lands on, as ASCII or SVG";
+
+const USAGE: &str = "Usage: day3 search [--dx MIN..MAX] [--dy MIN..MAX] [--topology T] [OPTIONS]
+       day3 render [SLOPE] [--svg PATH] [--topology T] [OPTIONS]
+
+    search          rank every slope in a range by the trees it runs into
+    render          draw the map with the cells a slope lands on marked O, or X for trees
+    SLOPE           e.g. \"right 1 down 3\", \"left 2 down 1\" or 1/3, defaults to right 3 down 1
+    --dx            columns moved each step, negative for left, defaults to -7..7
+    --dy            rows moved each step, negative for up, defaults to 1..2
+    --topology      how the map's edges behave: wrap repeats it sideways, bounded stops at
+                    any edge, mirror reflects off the sides and torus:STEPS repeats it every
+                    way for STEPS moves, defaults to wrap
+    --svg           also write the rendering to PATH as SVG, - for stdout";
+
+#[derive(Eq, PartialEq)]
+enum Command {
+    Search,
+    Render,
+}
+
+struct Options {
+    command: Command,
+    slope: Option<Movement>,
+    svg: Option<PathBuf>,
+    dx: Option<RangeInclusive<isize>>,
+    dy: Option<RangeInclusive<isize>>,
+    topology: Topology,
+}
+
+#[derive(Serialize)]
+struct Summary<'a> {
+    safest: Option<&'a Trial>,
+    worst: Option<&'a Trial>,
+    ranking: &'a [Trial],
+}
+
+/// Reads an inclusive range written as `MIN..MAX`, or a single number.
+fn parse_range(value: &str) -> Result<RangeInclusive<isize>, String> {
+    let number = |n: &str| {
+        n.parse::<isize>()
+            .map_err(|_| format!("{} is not a whole number of cells", n))
+    };
+    let range = match value.split_once("..") {
+        Some((min, max)) => number(min)?..=number(max)?,
+        None => number(value)?..=number(value)?,
+    };
+    if range.is_empty() {
+        return Err(format!("{} is an empty range", value));
+    }
+    Ok(range)
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut args = args.iter();
+    let mut options = Options {
+        command: match args.next().map(|a| a.as_str()) {
+            Some("search") => Command::Search,
+            Some("render") => Command::Render,
+            x => return Err(format!("Unknown command {}", x.unwrap_or_default())),
+        },
+        slope: None,
+        svg: None,
+        dx: None,
+        dy: None,
+        topology: Topology::default(),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dx" => {
+                let value = args.next().ok_or("--dx requires a value")?;
+                options.dx = Some(parse_range(value)?);
+            }
+            "--dy" => {
+                let value = args.next().ok_or("--dy requires a value")?;
+                options.dy = Some(parse_range(value)?);
+            }
+            "--topology" | "-t" => {
+                let value = args.next().ok_or("--topology requires a value")?;
+                options.topology = Topology::parse(value)?;
+            }
+            "--svg" => {
+                let value = args.next().ok_or("--svg requires a value")?;
+                options.svg = Some(PathBuf::from(value));
+            }
+            x if x.starts_with('-') && Movement::parse(x).is_err() => {
+                return Err(format!("Unknown option {}", x))
+            }
+            x if options.slope.is_none() => options.slope = Some(Movement::parse(x)?),
+            x => return Err(format!("Unexpected argument {}", x)),
+        }
+    }
+
+    if options.command != Command::Render && (options.slope.is_some() || options.svg.is_some()) {
+        return Err("A slope and --svg can only be used with render".to_string());
+    }
+
+    if options.command != Command::Search && (options.dx.is_some() || options.dy.is_some()) {
+        return Err("--dx and --dy can only be used with search".to_string());
+    }
+
+    Ok(options)
+}
+
+fn print_ranking(ranking: &Ranking, format: Format) {
+    match format {
+        Format::Text => {
+            for (label, trial) in [("safest", ranking.safest()), ("worst", ranking.worst())] {
+                if let Some(trial) = trial {
+                    println!("{0}\t{1}\t{2} trees", label, trial.slope, trial.trees);
+                }
+            }
+            println!();
+            for (i, trial) in ranking.trials.iter().enumerate() {
+                println!("{0}\t{1}\t{2} trees", i + 1, trial.slope, trial.trees);
+            }
+        }
+        Format::Json => report::print_json(&Summary {
+            safest: ranking.safest(),
+            worst: ranking.worst(),
+            ranking: &ranking.trials,
+        }),
+    }
+}
+
+fn export_svg(
+    path: &Path,
+    map: &Grid<bool>,
+    slope: &Movement,
+    topology: Topology,
+) -> Result<(), String> {
+    let result = if input::is_stdin(path) {
+        render::write_svg(io::stdout(), map, slope, topology)
+    } else {
+        File::create(path)
+            .and_then(|file| render::write_svg(BufWriter::new(file), map, slope, topology))
+    };
+    result.map_err(|e| format!("Unable to write the rendering {}: {}", path.display(), e))
+}
+
+#[derive(Serialize)]
+struct Rendering {
+    slope: String,
+    trees: i64,
+    map: Vec<String>,
+}
+
+fn print_rendering(map: &Grid<bool>, options: &Options, format: Format) -> Result<(), String> {
+    let slope = options.slope.unwrap_or(day3::PART1);
+
+    // SVG written to stdout replaces the usual drawing rather than following it.
+    if !options.svg.as_deref().is_some_and(input::is_stdin) {
+        let drawing = render::ascii(map, &slope, options.topology)?;
+        match format {
+            Format::Text => print!("{}", drawing),
+            Format::Json => report::print_json(&Rendering {
+                slope: slope.to_string(),
+                trees: day3::map_descent_on(map, &slope, options.topology)?,
+                map: drawing.lines().map(String::from).collect(),
+            }),
+        }
+    }
+
+    match &options.svg {
+        Some(path) => export_svg(path, map, &slope, options.topology),
+        None => Ok(()),
+    }
+}
+
+fn run(options: Options, shared: &CommandOptions) -> Result<(), String> {
+    let map = shared
+        .load(&day3::DAY, day3::parse)
+        .map_err(|e| e.to_string())?;
+
+    match options.command {
+        Command::Search => {
+            let ranking = search::rank(
+                &map,
+                options.dx.clone().unwrap_or(-7..=7),
+                options.dy.clone().unwrap_or(1..=2),
+                options.topology,
+            );
+            if ranking.trials.is_empty() {
+                return Err("No slope in the range ever ends a run on this topology".to_string());
+            }
+            print_ranking(&ranking, shared.format);
+            Ok(())
+        }
+        Command::Render => print_rendering(&map, &options, shared.format),
+    }
+}
+
+/// Explores the slopes down the tree map.
+pub fn main(args: &[String]) -> i32 {
+    runner::command(&day3::DAY, USAGE, args, parse_args, run)
+}
@@ -0,0 +1,187 @@
+pub mod render;
+pub mod search;
+pub mod topology;
+
+use common::grid::Grid;
+use common::{Answer, Day, Error, Example, Input, ParseError, Part};
+use std::fmt;
+use topology::Topology;
+
+/// How far the toboggan travels each step: `x` columns right (left when negative) and `y` rows
+/// down (up when negative).  A step of several rows gives fractional slopes, e.g. right 1 down 3.
+/// The toboggan only lands where a step ends, so right 2 down 2 skips the cells right 1 down 1
+/// lands on.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Movement {
+    x: isize,
+    y: isize,
+}
+
+impl Movement {
+    /// Fails for a step that doesn't move at all.  Whether a step suits a map's edges is up to
+    /// its `Topology`.
+    pub fn new(x: isize, y: isize) -> Result<Movement, String> {
+        if x == 0 && y == 0 {
+            return Err(format!("{} never moves", Movement { x, y }));
+        }
+        Ok(Movement { x, y })
+    }
+
+    /// Reads a slope written as `right 1 down 3`, `left 2 down 1` or `DX/DY`.
+    pub fn parse(value: &str) -> Result<Movement, String> {
+        let number = |n: &str| {
+            n.parse::<isize>()
+                .map_err(|_| format!("{} is not a whole number of cells", n))
+        };
+        let words = value.split_whitespace().collect::<Vec<_>>();
+
+        let (x, y) = match words.as_slice() {
+            [across, dx, along, dy] => {
+                let x = match *across {
+                    "right" => number(dx)?,
+                    "left" => -number(dx)?,
+                    _ => return Err(format!("Expected right or left but found {}", across)),
+                };
+                let y = match *along {
+                    "down" => number(dy)?,
+                    "up" => -number(dy)?,
+                    _ => return Err(format!("Expected down or up but found {}", along)),
+                };
+                (x, y)
+            }
+            [fraction] => match fraction.split_once('/') {
+                Some((dx, dy)) => (number(dx)?, number(dy)?),
+                None => return Err(format!("Unknown slope {}", value)),
+            },
+            _ => return Err(format!("Unknown slope {}", value)),
+        };
+
+        Movement::new(x, y)
+    }
+
+    pub fn x(&self) -> isize {
+        self.x
+    }
+
+    pub fn y(&self) -> isize {
+        self.y
+    }
+}
+
+impl fmt::Display for Movement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            if self.x < 0 { "left" } else { "right" },
+            self.x.abs(),
+            if self.y < 0 { "up" } else { "down" },
+            self.y.abs()
+        )
+    }
+}
+
+pub fn parse_row(line: &str) -> Result<Vec<bool>, ParseError> {
+    if line.is_empty() {
+        return Err(ParseError::new(1, line, "a row of '.' and '#'"));
+    }
+
+    line.char_indices()
+        .map(|(i, c)| match c {
+            '.' => Ok(false),
+            '#' => Ok(true),
+            _ => Err(ParseError::at(
+                line,
+                &line[i..i + c.len_utf8()],
+                "'.' or '#'",
+            )),
+        })
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<Grid<bool>, ParseError> {
+    Grid::parse(input, parse_row)
+}
+
+/// The cells the toboggan lands on sliding down `map` from the top left, as positions on the
+/// plane `topology` lays the map out over.  Columns run past the map's width or below zero when
+/// it repeats sideways, and rows past its height when it repeats downwards.  Fails when
+/// `topology` would never end the run.
+pub fn descent<'a>(
+    map: &'a Grid<bool>,
+    movement: &Movement,
+    topology: Topology,
+) -> Result<impl Iterator<Item = (isize, isize)> + 'a, String> {
+    topology.check(movement)?;
+    let (dx, dy) = (movement.x, movement.y);
+    Ok((0..)
+        .take(topology.steps().map_or(usize::MAX, |s| s.saturating_add(1)))
+        .map(move |step: isize| (step * dx, step * dy))
+        .take_while(move |(x, y)| topology.cell(map, *x, *y).is_some()))
+}
+
+/// Counts the trees hit sliding down `map` from the top left with the edges behaving as
+/// `topology` says.
+pub fn map_descent_on(
+    map: &Grid<bool>,
+    movement: &Movement,
+    topology: Topology,
+) -> Result<i64, String> {
+    Ok(descent(map, movement, topology)?
+        .filter(|(x, y)| topology.cell(map, *x, *y) == Some(true))
+        .count() as i64)
+}
+
+/// Counts the trees hit sliding down `map` from the top left, wrapping around horizontally in
+/// whichever direction the slope leads.  Fails for slopes that never descend.
+pub fn map_descent(map: &Grid<bool>, movement: &Movement) -> Result<i64, String> {
+    map_descent_on(map, movement, Topology::Wrap)
+}
+
+/// The slope part 1 asks about.
+pub const PART1: Movement = Movement { x: 3, y: 1 };
+
+pub fn part1(map: &Grid<bool>) -> Result<i64, String> {
+    map_descent(map, &PART1)
+}
+
+pub fn part2(map: &Grid<bool>) -> Result<i64, String> {
+    let routes = [
+        Movement { x: 1, y: 1 },
+        Movement { x: 3, y: 1 },
+        Movement { x: 5, y: 1 },
+        Movement { x: 7, y: 1 },
+        Movement { x: 1, y: 2 },
+    ];
+
+    routes.iter().map(|r| map_descent(map, r)).product()
+}
+
+fn solve_part1(input: &str) -> Result<Answer, Error> {
+    part1(&parse(input)?).map_err(Error::NoSolution)
+}
+
+fn solve_part2(input: &str) -> Result<Answer, Error> {
+    part2(&parse(input)?).map_err(Error::NoSolution)
+}
+
+pub static DAY: Day = Day {
+    number: 3,
+    title: "Toboggan Trajectory",
+    part1: Some(solve_part1),
+    part2: Some(solve_part2),
+    examples: &[
+        Example {
+            part: Part::One,
+            input: Input::Example(1),
+            expected: 7,
+            solver: None,
+        },
+        Example {
+            part: Part::Two,
+            input: Input::Example(1),
+            expected: 336,
+            solver: None,
+        },
+    ],
+};
@@ -0,0 +1,175 @@
+use crate::topology::Topology;
+use crate::{descent, map_descent_on, Movement};
+use common::grid::Grid;
+use std::collections::HashSet;
+use std::io;
+use std::ops::RangeInclusive;
+
+/// Pixels along each side of a cell in the SVG.
+const CELL: usize = 10;
+
+/// The part of the plane a drawing covers: every copy of the map the path passes through,
+/// numbered from 0 for the one it starts in.
+struct View {
+    tiles_x: RangeInclusive<isize>,
+    tiles_y: RangeInclusive<isize>,
+    width: isize,
+    height: isize,
+}
+
+impl View {
+    fn new(map: &Grid<bool>, visits: &[(isize, isize)]) -> Self {
+        let (width, height) = (map.width().max(1) as isize, map.height().max(1) as isize);
+        let span = |tiles: Vec<isize>| {
+            tiles.iter().copied().min().unwrap_or(0)..=tiles.iter().copied().max().unwrap_or(0)
+        };
+        View {
+            tiles_x: span(visits.iter().map(|(x, _)| x.div_euclid(width)).collect()),
+            tiles_y: span(visits.iter().map(|(_, y)| y.div_euclid(height)).collect()),
+            width,
+            height,
+        }
+    }
+
+    fn columns(&self) -> RangeInclusive<isize> {
+        self.tiles_x.start() * self.width..=(self.tiles_x.end() + 1) * self.width - 1
+    }
+
+    fn rows(&self) -> RangeInclusive<isize> {
+        self.tiles_y.start() * self.height..=(self.tiles_y.end() + 1) * self.height - 1
+    }
+
+    /// Pixel offset of the top left of the cell at `(x, y)`.
+    fn pixel(&self, x: isize, y: isize) -> (usize, usize) {
+        (
+            (x - self.columns().start()) as usize * CELL,
+            (y - self.rows().start()) as usize * CELL,
+        )
+    }
+}
+
+/// Draws the map repeated as often as the path needs, marking each cell the toboggan lands on
+/// with `O` when it is open and `X` when it hits a tree, as in the puzzle text.
+pub fn ascii(map: &Grid<bool>, movement: &Movement, topology: Topology) -> Result<String, String> {
+    let visits = descent(map, movement, topology)?.collect::<Vec<_>>();
+    let view = View::new(map, &visits);
+    let visits = visits.into_iter().collect::<HashSet<_>>();
+
+    let mut out = String::new();
+    for y in view.rows() {
+        for x in view.columns() {
+            out.push(match (visits.contains(&(x, y)), topology.cell(map, x, y)) {
+                (true, Some(true)) => 'X',
+                (true, _) => 'O',
+                (false, Some(true)) => '#',
+                (false, _) => '.',
+            });
+        }
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Draws the same picture as `ascii` as an SVG image, with dashed lines between the repeated
+/// copies of the map and the original copy outlined.
+pub fn write_svg<W: io::Write>(
+    mut writer: W,
+    map: &Grid<bool>,
+    movement: &Movement,
+    topology: Topology,
+) -> io::Result<()> {
+    let invalid = |e| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let visits = descent(map, movement, topology)
+        .map_err(invalid)?
+        .collect::<Vec<_>>();
+    let trees = map_descent_on(map, movement, topology).map_err(invalid)?;
+    let view = View::new(map, &visits);
+    let width = view.columns().count() * CELL;
+    let height = view.rows().count() * CELL;
+
+    writeln!(
+        writer,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"##,
+        width, height
+    )?;
+    writeln!(writer, "<title>{}: {} trees</title>", movement, trees)?;
+    writeln!(
+        writer,
+        r##"<rect width="{}" height="{}" fill="#f4f1e8"/>"##,
+        width, height
+    )?;
+
+    for y in view.rows() {
+        for x in view.columns() {
+            if topology.cell(map, x, y) == Some(true) {
+                let (px, py) = view.pixel(x, y);
+                writeln!(
+                    writer,
+                    r##"<rect x="{}" y="{}" width="{2}" height="{2}" fill="#2d6a4f"/>"##,
+                    px, py, CELL
+                )?;
+            }
+        }
+    }
+
+    for tile in *view.tiles_x.start() + 1..=*view.tiles_x.end() {
+        let (px, _) = view.pixel(tile * view.width, 0);
+        writeln!(
+            writer,
+            r##"<line x1="{0}" y1="0" x2="{0}" y2="{1}" stroke="#888" stroke-dasharray="4 2"/>"##,
+            px, height
+        )?;
+    }
+    for tile in *view.tiles_y.start() + 1..=*view.tiles_y.end() {
+        let (_, py) = view.pixel(0, tile * view.height);
+        writeln!(
+            writer,
+            r##"<line x1="0" y1="{0}" x2="{1}" y2="{0}" stroke="#888" stroke-dasharray="4 2"/>"##,
+            py, width
+        )?;
+    }
+    let (px, py) = view.pixel(0, 0);
+    writeln!(
+        writer,
+        r##"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="#444" stroke-width="2"/>"##,
+        px,
+        py,
+        map.width() * CELL,
+        map.height() * CELL
+    )?;
+
+    let centre = |x: isize, y: isize| {
+        let (px, py) = view.pixel(x, y);
+        (px + CELL / 2, py + CELL / 2)
+    };
+    let points = visits
+        .iter()
+        .map(|(x, y)| {
+            let (cx, cy) = centre(*x, *y);
+            format!("{},{}", cx, cy)
+        })
+        .collect::<Vec<_>>();
+    writeln!(
+        writer,
+        r##"<polyline points="{}" fill="none" stroke="#577590" stroke-width="2"/>"##,
+        points.join(" ")
+    )?;
+    for (x, y) in visits.iter() {
+        let (cx, cy) = centre(*x, *y);
+        let fill = if topology.cell(map, *x, *y) == Some(true) {
+            "#d62828"
+        } else {
+            "#f8f9fa"
+        };
+        writeln!(
+            writer,
+            r##"<circle cx="{}" cy="{}" r="{}" fill="{}" stroke="#577590"/>"##,
+            cx,
+            cy,
+            CELL / 3,
+            fill
+        )?;
+    }
+
+    writeln!(writer, "</svg>")
+}
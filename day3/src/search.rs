@@ -0,0 +1,55 @@
+use crate::topology::Topology;
+use crate::{map_descent_on, Movement};
+use common::grid::Grid;
+use serde::Serialize;
+use std::ops::RangeInclusive;
+
+/// How many trees one slope runs into.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Trial {
+    pub slope: String,
+    pub dx: isize,
+    pub dy: isize,
+    pub trees: i64,
+}
+
+/// Every slope tried, from fewest trees to most.  Slopes hitting as many trees as each other
+/// keep the order they were tried in: `dy` first, then `dx`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Ranking {
+    pub trials: Vec<Trial>,
+}
+
+impl Ranking {
+    pub fn safest(&self) -> Option<&Trial> {
+        self.trials.first()
+    }
+
+    pub fn worst(&self) -> Option<&Trial> {
+        self.trials.last()
+    }
+}
+
+/// Slides down `map` along every slope within `dx` and `dy`, skipping those `topology` can't end.
+pub fn rank(
+    map: &Grid<bool>,
+    dx: RangeInclusive<isize>,
+    dy: RangeInclusive<isize>,
+    topology: Topology,
+) -> Ranking {
+    let mut trials = dy
+        .flat_map(|y| dx.clone().map(move |x| (x, y)))
+        .filter_map(|(x, y)| Movement::new(x, y).ok())
+        .filter_map(|movement| {
+            Some(Trial {
+                slope: movement.to_string(),
+                dx: movement.x(),
+                dy: movement.y(),
+                trees: map_descent_on(map, &movement, topology).ok()?,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    trials.sort_by_key(|t| t.trees);
+    Ranking { trials }
+}
@@ -0,0 +1,170 @@
+use crate::Movement;
+use common::grid::Grid;
+
+/// How the map behaves at its edges.  Every topology lays the map out over an endless plane, so
+/// a run can be described by where it is on that plane.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Topology {
+    /// The map repeats sideways forever and the run ends at the bottom, as in the puzzle.
+    #[default]
+    Wrap,
+    /// The run ends at whichever edge of the map it crosses first.
+    Bounded,
+    /// The map repeats in every direction, so the run only ends after `steps` moves.
+    Torus { steps: usize },
+    /// The sides reflect like mirrors, so every other copy of the map is flipped, and the run
+    /// ends at the bottom.
+    Mirror,
+}
+
+impl Topology {
+    /// Reads `wrap`, `bounded`, `mirror` or `torus:STEPS`.
+    pub fn parse(value: &str) -> Result<Topology, String> {
+        match value {
+            "wrap" => Ok(Topology::Wrap),
+            "bounded" => Ok(Topology::Bounded),
+            "mirror" => Ok(Topology::Mirror),
+            x => match x.strip_prefix("torus:") {
+                Some(steps) => steps
+                    .parse()
+                    .map(|steps| Topology::Torus { steps })
+                    .map_err(|_| format!("{} is not a number of steps", steps)),
+                None => Err(format!("Unknown topology {}", x)),
+            },
+        }
+    }
+
+    /// Fails for steps that would slide forever without the run ending: those that never
+    /// descend when only the bottom of the map ends it.
+    pub fn check(&self, movement: &Movement) -> Result<(), String> {
+        match self {
+            Topology::Wrap | Topology::Mirror if movement.y() <= 0 => Err(format!(
+                "{} never descends, so it never reaches the bottom of the map",
+                movement
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether a tree stands at `(x, y)` on the plane, or `None` if that is off the map.
+    pub fn cell(&self, map: &Grid<bool>, x: isize, y: isize) -> Option<bool> {
+        let (width, height) = (map.width() as isize, map.height() as isize);
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        match self {
+            Topology::Wrap if (0..height).contains(&y) => Some(map.get_wrapping(x, y)),
+            Topology::Bounded => map.get_signed(x, y),
+            Topology::Torus { .. } => Some(map.get_wrapping(x, y)),
+            Topology::Mirror if (0..height).contains(&y) => {
+                let x = x.rem_euclid(2 * width);
+                map.get_signed(if x < width { x } else { 2 * width - 1 - x }, y)
+            }
+            _ => None,
+        }
+    }
+
+    /// The most moves a run can make before it ends regardless of the map.
+    pub fn steps(&self) -> Option<usize> {
+        match self {
+            Topology::Torus { steps } => Some(*steps),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{descent, map_descent_on, parse};
+
+    // Trees only in the first column, so a run's trees show which copies it crossed.
+    const MAP: &str = "#..\n#..\n#..\n#..\n";
+
+    fn map() -> Grid<bool> {
+        parse(MAP).unwrap()
+    }
+
+    #[test]
+    fn parses_every_topology() {
+        assert_eq!(Topology::parse("wrap"), Ok(Topology::Wrap));
+        assert_eq!(Topology::parse("bounded"), Ok(Topology::Bounded));
+        assert_eq!(Topology::parse("mirror"), Ok(Topology::Mirror));
+        assert_eq!(Topology::parse("torus:5"), Ok(Topology::Torus { steps: 5 }));
+        assert!(Topology::parse("torus:-1").is_err());
+        assert!(Topology::parse("sphere").is_err());
+    }
+
+    #[test]
+    fn mirror_flips_every_other_copy() {
+        let map = map();
+        let row = |topology: Topology| {
+            (-6..9)
+                .map(|x| topology.cell(&map, x, 0) == Some(true))
+                .collect::<Vec<_>>()
+        };
+        // Columns -6..9 cross five copies: flipped, plain, flipped, plain, flipped.
+        let mirrored = row(Topology::Mirror);
+        let trees = (-6..9)
+            .zip(mirrored)
+            .filter(|(_, tree)| *tree)
+            .map(|(x, _)| x)
+            .collect::<Vec<_>>();
+        assert_eq!(trees, vec![-6, -1, 0, 5, 6]);
+        assert_eq!(Topology::Mirror.cell(&map, 0, 4), None);
+    }
+
+    #[test]
+    fn bounded_ends_at_any_edge() {
+        let map = map();
+        assert_eq!(Topology::Bounded.cell(&map, 3, 0), None);
+        assert_eq!(Topology::Bounded.cell(&map, -1, 0), None);
+        let right = Movement::new(1, 1).unwrap();
+        assert_eq!(descent(&map, &right, Topology::Bounded).unwrap().count(), 3);
+    }
+
+    #[test]
+    fn torus_stops_after_its_steps() {
+        let map = map();
+        let down = Movement::new(0, 1).unwrap();
+        for steps in [0, 1, 4, 9].iter() {
+            let topology = Topology::Torus { steps: *steps };
+            // The start counts as a landing, then one more for each move.
+            assert_eq!(descent(&map, &down, topology).unwrap().count(), steps + 1);
+            assert_eq!(map_descent_on(&map, &down, topology), Ok(*steps as i64 + 1));
+        }
+        assert_eq!(map_descent_on(&map, &down, Topology::Wrap), Ok(4));
+    }
+
+    #[test]
+    fn only_runs_ending_at_the_bottom_must_descend() {
+        let map = map();
+        let across = Movement::new(1, 0).unwrap();
+        let up = Movement::new(-1, -1).unwrap();
+        for movement in [across, up].iter() {
+            assert!(map_descent_on(&map, movement, Topology::Wrap).is_err());
+            assert!(map_descent_on(&map, movement, Topology::Mirror).is_err());
+        }
+        // Sliding across the first row leaves the map at its right edge.
+        assert_eq!(map_descent_on(&map, &across, Topology::Bounded), Ok(1));
+        assert_eq!(map_descent_on(&map, &up, Topology::Bounded), Ok(1));
+        let torus = Topology::Torus { steps: 5 };
+        assert_eq!(map_descent_on(&map, &across, torus), Ok(2));
+        assert!(Movement::new(0, 0).is_err());
+    }
+
+    #[test]
+    fn empty_maps_have_no_cells() {
+        let empty = Grid::new(0, 0, false);
+        for topology in [
+            Topology::Wrap,
+            Topology::Torus { steps: 3 },
+            Topology::Mirror,
+        ]
+        .iter()
+        {
+            assert_eq!(topology.cell(&empty, 0, 0), None);
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use common::input;
+use common::report::{self, Format};
+use common::runner::{self, CommandOptions};
+use day4::explain;
+use day4::passport;
+use day4::schema::{Matching, Schema};
+use day4::stats::{self, Statistics};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+
+/// The subcommands, as the runner's `--help` lists them.
+pub const COMMANDS: &str =
+    "    validate        count the complete passports and those with acceptable values
+    explain         show every field of each passport and why it failed
+    stats           summarize how often each field appears and fails
+    export          write the batch as typed passports in JSON, CSV or batch format";
+
+const USAGE: &str = "Usage: day4 validate|explain|stats [--schema PATH] [--strict|--lenient]
+                                   [--invalid] [OPTIONS]
+       day4 export [--to json|csv|batch] [OPTIONS]
+
+    validate        count the passports with every required field and those whose fields
+                    all hold acceptable values
+    explain         show every field of each passport, the rule it was checked against and
+                    why it failed
+    stats           summarize the batch: how often each field appears, the spread of eye
+                    colors, height units and birth years, the commonest failures and any
+                    fields the schema doesn't know
+    export          convert the batch to typed passports and write them to stdout, keeping
+                    values in the wrong shape as written and reporting passports with
+                    unknown fields
+    --schema        TOML or JSON file of required fields and rules, defaults to the puzzle's
+    --strict        match rules against whole values, whatever the schema says
+    --lenient       let rules match anywhere in a value and numbers carry a sign or leading
+                    zeros, whatever the schema says
+    --invalid       only explain the passports that fail
+    --to            what export writes: json for one object per line, csv or the batch
+                    format, defaults to json";
+
+#[derive(Eq, PartialEq)]
+enum Command {
+    Validate,
+    Explain,
+    Stats,
+    Export,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Export {
+    Json,
+    Csv,
+    Batch,
+}
+
+struct Options {
+    command: Command,
+    invalid: bool,
+    matching: Option<Matching>,
+    export: Option<Export>,
+    schema: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+struct Counts {
+    passports: usize,
+    complete: usize,
+    valid: usize,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut args = args.iter();
+    let mut options = Options {
+        command: match args.next().map(|a| a.as_str()) {
+            Some("validate") => Command::Validate,
+            Some("explain") => Command::Explain,
+            Some("stats") => Command::Stats,
+            Some("export") => Command::Export,
+            x => return Err(format!("Unknown command {}", x.unwrap_or_default())),
+        },
+        invalid: false,
+        matching: None,
+        export: None,
+        schema: None,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--schema" | "-s" => {
+                let value = args.next().ok_or("--schema requires a value")?;
+                options.schema = Some(PathBuf::from(value));
+            }
+            "--invalid" => options.invalid = true,
+            "--strict" => options.matching = Some(Matching::Strict),
+            "--lenient" => options.matching = Some(Matching::Lenient),
+            "--to" => {
+                let value = args.next().ok_or("--to requires a value")?;
+                options.export = Some(match value.as_str() {
+                    "json" => Export::Json,
+                    "csv" => Export::Csv,
+                    "batch" => Export::Batch,
+                    x => return Err(format!("Unknown export {}", x)),
+                });
+            }
+            x => return Err(format!("Unknown option {}", x)),
+        }
+    }
+
+    if options.invalid && options.command != Command::Explain {
+        return Err("--invalid can only be used with explain".to_string());
+    }
+
+    if options.command == Command::Export
+        && (options.schema.is_some() || options.matching.is_some())
+    {
+        return Err("--schema, --strict and --lenient can't be used with export".to_string());
+    }
+
+    if options.export.is_some() && options.command != Command::Export {
+        return Err("--to can only be used with export".to_string());
+    }
+
+    Ok(options)
+}
+
+/// Writes every passport, reporting records with fields the puzzle doesn't know on stderr.
+fn export(shared: &CommandOptions, to: Export) -> Result<(), String> {
+    let batch = shared
+        .load(&day4::DAY, passport::parse_passports)
+        .map_err(|e| e.to_string())?;
+    let path = shared.path(&day4::DAY);
+    for e in batch.rejected {
+        eprintln!("skipped {}", e.in_file(input::source(&path)));
+    }
+    let passports = batch.passports;
+    let stdout = io::stdout();
+    let writer = stdout.lock();
+    match to {
+        Export::Json => passport::write_json(writer, &passports).map_err(|e| e.to_string()),
+        Export::Csv => passport::write_csv(writer, &passports).map_err(|e| e.to_string()),
+        Export::Batch => passport::write_batch(writer, &passports).map_err(|e| e.to_string()),
+    }
+}
+
+fn print_histogram(title: &str, counts: &BTreeMap<String, usize>) {
+    println!("{}", title);
+    for (value, count) in counts {
+        println!("  {0}\t{1}", value, count);
+    }
+}
+
+fn print_statistics(statistics: &Statistics) {
+    println!(
+        "{0} passports\t{1} valid\n",
+        statistics.passports, statistics.valid
+    );
+    println!("field presence");
+    for presence in statistics.presence.iter() {
+        println!(
+            "  {0}\t{1}\t{2:.1}%",
+            presence.field,
+            presence.present,
+            presence.rate * 100.0
+        );
+    }
+    print_histogram("eye colors", &statistics.eye_colors);
+    print_histogram("height units", &statistics.height_units);
+    print_histogram("birth years", &statistics.birth_years);
+    println!("failures");
+    for failure in statistics.failures.iter() {
+        println!(
+            "  {0}\t{1}\t{2}",
+            failure.field, failure.failure, failure.passports
+        );
+    }
+    print_histogram("unknown fields", &statistics.unknown_fields);
+}
+
+fn run(options: Options, shared: &CommandOptions) -> Result<(), String> {
+    if options.command == Command::Export {
+        return export(shared, options.export.unwrap_or(Export::Json));
+    }
+
+    let mut schema = match &options.schema {
+        Some(path) => Schema::load(path)?,
+        None => Schema::default(),
+    };
+    if let Some(matching) = options.matching {
+        schema.matching = matching;
+    }
+
+    let passports = shared
+        .load(&day4::DAY, day4::parse)
+        .map_err(|e| e.to_string())?;
+
+    match options.command {
+        Command::Explain => {
+            let explanations = explain::explanations(&passports, &schema);
+            for explanation in explanations
+                .iter()
+                .filter(|e| !(options.invalid && e.valid))
+            {
+                match shared.format {
+                    Format::Text => println!("{}", explain::table(explanation)),
+                    Format::Json => report::print_json(explanation),
+                }
+            }
+        }
+        Command::Stats => {
+            let statistics = stats::analyze(&passports, &schema);
+            match shared.format {
+                Format::Text => print_statistics(&statistics),
+                Format::Json => report::print_json(&statistics),
+            }
+        }
+        Command::Validate | Command::Export => {
+            let counts = Counts {
+                passports: passports.len(),
+                complete: day4::complete(&passports, &schema),
+                valid: day4::valid(&passports, &schema),
+            };
+            match shared.format {
+                Format::Text => println!(
+                    "{0} passports\t{1} complete\t{2} valid",
+                    counts.passports, counts.complete, counts.valid
+                ),
+                Format::Json => report::print_json(&counts),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the passport batch against a schema.
+pub fn main(args: &[String]) -> i32 {
+    runner::command(&day4::DAY, USAGE, args, parse_args, run)
+}
@@ -0,0 +1,106 @@
+use crate::schema::{Failure, Schema};
+use crate::Fields;
+use serde::Serialize;
+
+/// One field of a passport checked against its rule.
+#[derive(Clone, Debug, Serialize)]
+pub struct Check {
+    pub field: String,
+    pub rule: String,
+    pub value: Option<String>,
+    pub passed: bool,
+    pub failure: Option<Failure>,
+}
+
+/// Every field of one passport checked, numbered from 1 in batch order.
+#[derive(Clone, Debug, Serialize)]
+pub struct Explanation {
+    pub passport: usize,
+    pub valid: bool,
+    pub checks: Vec<Check>,
+}
+
+/// Checks every field `schema` knows about on `passport`.
+pub fn explain(number: usize, passport: &Fields, schema: &Schema) -> Explanation {
+    let checks = schema
+        .fields()
+        .map(|field| {
+            let rule = match schema.rules.get(field) {
+                Some(rule) => rule.to_string(),
+                None if schema.required.contains(field) => "present".to_string(),
+                None => "optional".to_string(),
+            };
+            let failure = schema.check(passport, field).err();
+            Check {
+                field: field.clone(),
+                rule,
+                value: passport.get(field).cloned(),
+                passed: failure.is_none(),
+                failure,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Explanation {
+        passport: number,
+        valid: checks.iter().all(|c| c.passed),
+        checks,
+    }
+}
+
+pub fn explanations(passports: &[Fields], schema: &Schema) -> Vec<Explanation> {
+    passports
+        .iter()
+        .enumerate()
+        .map(|(i, p)| explain(i + 1, p, schema))
+        .collect()
+}
+
+/// Lays an explanation out as a table with a row per field.
+pub fn table(explanation: &Explanation) -> String {
+    let rows = explanation
+        .checks
+        .iter()
+        .map(|c| {
+            [
+                c.field.clone(),
+                c.rule.clone(),
+                c.value.clone().unwrap_or_else(|| "-".to_string()),
+                match c.failure {
+                    None => "pass".to_string(),
+                    Some(failure) => format!("FAIL: {}", failure),
+                },
+            ]
+        })
+        .collect::<Vec<_>>();
+    let header = ["field", "rule", "value", "result"].map(String::from);
+
+    let mut widths = [0; 4];
+    for row in rows.iter().chain(std::iter::once(&header)) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = format!(
+        "passport {}: {}\n",
+        explanation.passport,
+        if explanation.valid {
+            "valid"
+        } else {
+            "invalid"
+        }
+    );
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        let line = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:1$}", cell, width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        out.push_str("  ");
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,143 @@
+#[macro_use]
+extern crate lazy_static;
+
+pub mod explain;
+pub mod passport;
+pub mod schema;
+pub mod stats;
+
+use common::{Answer, Day, Error, Example, Input, ParseError, Part};
+use schema::Schema;
+use std::collections::HashMap;
+
+lazy_static! {
+    static ref PUZZLE: Schema = Schema::default();
+}
+
+/// A passport's fields keyed by their three letter code, e.g. `byr` or `hcl`.
+pub type Fields = HashMap<String, String>;
+
+/// One `key:value` field from a batch file, along with the line it was found on.
+#[derive(Copy, Clone, Debug)]
+pub struct Token<'a> {
+    pub line: usize,
+    pub text: &'a str,
+    pub key: &'a str,
+    pub value: &'a str,
+}
+
+/// Splits the batch file into passports separated by blank lines, rejecting tokens which aren't
+/// `key:value` and fields given twice in one passport.
+pub fn tokenize(input: &str) -> Result<Vec<Vec<Token<'_>>>, ParseError> {
+    let mut records = vec![vec![]];
+    for (i, l) in input.lines().enumerate() {
+        if l.is_empty() {
+            records.push(vec![]);
+            continue;
+        }
+
+        for token in l.split_whitespace() {
+            let (key, value) = token
+                .split_once(':')
+                .filter(|(key, _)| !key.is_empty())
+                .ok_or_else(|| {
+                    ParseError::at(l, token, "a field such as key:value").on_line(i + 1)
+                })?;
+            let current = records.last_mut().unwrap();
+            if current.iter().any(|t: &Token| t.key == key) {
+                return Err(ParseError::at(
+                    l,
+                    token,
+                    format!("a field other than {}, which is already set", key),
+                )
+                .on_line(i + 1));
+            }
+            current.push(Token {
+                line: i + 1,
+                text: l,
+                key,
+                value,
+            });
+        }
+    }
+    Ok(records)
+}
+
+/// Reads the batch file, where passports are separated by blank lines.
+pub fn parse(input: &str) -> Result<Vec<Fields>, ParseError> {
+    Ok(tokenize(input)?
+        .into_iter()
+        .map(|record| {
+            record
+                .iter()
+                .map(|t| (t.key.to_string(), t.value.to_string()))
+                .collect()
+        })
+        .collect())
+}
+
+/// Whether every field holds a value the part 2 rules accept.
+pub fn is_valid(credential: &Fields) -> bool {
+    PUZZLE.is_valid(credential)
+}
+
+/// Whether all the required fields are present, `cid` being optional.
+pub fn has_fields(credential: &Fields) -> bool {
+    PUZZLE.has_fields(credential)
+}
+
+/// Passports with every field `schema` requires.
+pub fn complete(passports: &[Fields], schema: &Schema) -> usize {
+    passports.iter().filter(|p| schema.has_fields(p)).count()
+}
+
+/// Passports with every field `schema` requires, each holding a value it accepts.
+pub fn valid(passports: &[Fields], schema: &Schema) -> usize {
+    passports
+        .iter()
+        .filter(|p| schema.has_fields(p) && schema.is_valid(p))
+        .count()
+}
+
+pub fn part1(passports: &[Fields]) -> usize {
+    complete(passports, &PUZZLE)
+}
+
+pub fn part2(passports: &[Fields]) -> usize {
+    valid(passports, &PUZZLE)
+}
+
+fn solve_part1(input: &str) -> Result<Answer, Error> {
+    Ok(part1(&parse(input)?) as Answer)
+}
+
+fn solve_part2(input: &str) -> Result<Answer, Error> {
+    Ok(part2(&parse(input)?) as Answer)
+}
+
+pub static DAY: Day = Day {
+    number: 4,
+    title: "Passport Processing",
+    part1: Some(solve_part1),
+    part2: Some(solve_part2),
+    examples: &[
+        Example {
+            part: Part::One,
+            input: Input::Example(1),
+            expected: 2,
+            solver: None,
+        },
+        Example {
+            part: Part::Two,
+            input: Input::File("day4.part2.invalid.txt"),
+            expected: 0,
+            solver: None,
+        },
+        Example {
+            part: Part::Two,
+            input: Input::File("day4.part2.valid.txt"),
+            expected: 4,
+            solver: None,
+        },
+    ],
+};
@@ -0,0 +1,312 @@
+use crate::{tokenize, Token};
+use common::ParseError;
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Unit {
+    Cm,
+    In,
+}
+
+/// A height such as `183cm`, written that way in every export.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Height {
+    pub size: u32,
+    pub unit: Unit,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EyeColor {
+    Amb,
+    Blu,
+    Brn,
+    Gry,
+    Grn,
+    Hzl,
+    Oth,
+}
+
+/// A field's value: typed when it has the right shape, otherwise kept as written.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Field<T> {
+    Valid(T),
+    Other(String),
+}
+
+/// A passport with its fields typed where they have the right shape.  Which fields it needs and
+/// whether their values are acceptable is up to a `Schema`.  Every export writes a field as it
+/// appears in a batch file.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(from = "Written")]
+pub struct Passport {
+    pub birth_year: Option<Field<u32>>,
+    pub issue_year: Option<Field<u32>>,
+    pub expiration_year: Option<Field<u32>>,
+    pub height: Option<Field<Height>>,
+    pub hair_color: Option<Field<String>>,
+    pub eye_color: Option<Field<EyeColor>>,
+    pub passport_id: Option<Field<String>>,
+    pub country_id: Option<String>,
+}
+
+/// A passport as an export writes it, before its fields are typed.
+#[derive(Deserialize)]
+struct Written {
+    birth_year: Option<String>,
+    issue_year: Option<String>,
+    expiration_year: Option<String>,
+    height: Option<String>,
+    hair_color: Option<String>,
+    eye_color: Option<String>,
+    passport_id: Option<String>,
+    country_id: Option<String>,
+}
+
+/// The passports of a batch file, and why any records couldn't be read as one.
+#[derive(Debug, Default)]
+pub struct Batch {
+    pub passports: Vec<Passport>,
+    pub rejected: Vec<ParseError>,
+}
+
+impl<T> Field<T> {
+    fn read(value: &str, convert: fn(&str) -> Option<T>) -> Self {
+        match convert(value) {
+            Some(value) => Field::Valid(value),
+            None => Field::Other(value.to_string()),
+        }
+    }
+
+    /// The typed value, if the field has the right shape.
+    pub fn valid(&self) -> Option<&T> {
+        match self {
+            Field::Valid(value) => Some(value),
+            Field::Other(_) => None,
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Field<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Field::Valid(value) => write!(f, "{}", value),
+            Field::Other(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl<T: fmt::Display> Serialize for Field<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Unit::Cm => "cm",
+                Unit::In => "in",
+            }
+        )
+    }
+}
+
+impl fmt::Display for Height {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.size, self.unit)
+    }
+}
+
+impl FromStr for Height {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, ()> {
+        let (size, unit) = if let Some(size) = value.strip_suffix("cm") {
+            (size, Unit::Cm)
+        } else if let Some(size) = value.strip_suffix("in") {
+            (size, Unit::In)
+        } else {
+            return Err(());
+        };
+        Ok(Height {
+            size: digits(size).ok_or(())?,
+            unit,
+        })
+    }
+}
+
+impl fmt::Display for EyeColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", EyeColor::NAMES[*self as usize])
+    }
+}
+
+impl FromStr for EyeColor {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, ()> {
+        EyeColor::ALL
+            .iter()
+            .copied()
+            .find(|c| EyeColor::NAMES[*c as usize] == value)
+            .ok_or(())
+    }
+}
+
+impl From<Written> for Passport {
+    fn from(written: Written) -> Self {
+        Passport::from_values(|key| {
+            match key {
+                "byr" => &written.birth_year,
+                "iyr" => &written.issue_year,
+                "eyr" => &written.expiration_year,
+                "hgt" => &written.height,
+                "hcl" => &written.hair_color,
+                "ecl" => &written.eye_color,
+                "pid" => &written.passport_id,
+                "cid" => &written.country_id,
+                _ => return None,
+            }
+            .as_deref()
+        })
+    }
+}
+
+impl EyeColor {
+    pub const ALL: [EyeColor; 7] = [
+        EyeColor::Amb,
+        EyeColor::Blu,
+        EyeColor::Brn,
+        EyeColor::Gry,
+        EyeColor::Grn,
+        EyeColor::Hzl,
+        EyeColor::Oth,
+    ];
+    const NAMES: [&'static str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+}
+
+/// A number written with plain decimal digits.
+fn digits(value: &str) -> Option<u32> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+fn hair_color(value: &str) -> Option<String> {
+    let hex = value.len() == 7
+        && value.starts_with('#')
+        && value[1..]
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+    Some(value.to_string()).filter(|_| hex)
+}
+
+fn passport_id(value: &str) -> Option<String> {
+    Some(value.to_string()).filter(|v| v.len() == 9 && digits(v).is_some())
+}
+
+const FIELDS: [&str; 8] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+
+impl Passport {
+    /// Types the fields of a passport, given each one's value by its three letter code.
+    fn from_values<'a, F>(value: F) -> Passport
+    where
+        F: Fn(&str) -> Option<&'a str>,
+    {
+        Passport {
+            birth_year: value("byr").map(|v| Field::read(v, digits)),
+            issue_year: value("iyr").map(|v| Field::read(v, digits)),
+            expiration_year: value("eyr").map(|v| Field::read(v, digits)),
+            height: value("hgt").map(|v| Field::read(v, |v| v.parse().ok())),
+            hair_color: value("hcl").map(|v| Field::read(v, hair_color)),
+            eye_color: value("ecl").map(|v| Field::read(v, |v| v.parse().ok())),
+            passport_id: value("pid").map(|v| Field::read(v, passport_id)),
+            country_id: value("cid").map(String::from),
+        }
+    }
+
+    /// Builds a passport from the fields of one record in a batch file, failing on fields the
+    /// puzzle doesn't know.
+    pub fn from_tokens(tokens: &[Token]) -> Result<Passport, ParseError> {
+        if let Some(t) = tokens.iter().find(|t| !FIELDS.contains(&t.key)) {
+            return Err(
+                ParseError::at(t.text, t.key, format!("one of {}", FIELDS.join(", ")))
+                    .on_line(t.line),
+            );
+        }
+
+        Ok(Passport::from_values(|key| {
+            tokens.iter().find(|t| t.key == key).map(|t| t.value)
+        }))
+    }
+
+    /// The passport as one line of a batch file, leaving out the fields it doesn't have.
+    pub fn to_batch(&self) -> String {
+        let fields: [(&str, Option<String>); 8] = [
+            ("byr", self.birth_year.as_ref().map(Field::to_string)),
+            ("iyr", self.issue_year.as_ref().map(Field::to_string)),
+            ("eyr", self.expiration_year.as_ref().map(Field::to_string)),
+            ("hgt", self.height.as_ref().map(Field::to_string)),
+            ("hcl", self.hair_color.as_ref().map(Field::to_string)),
+            ("ecl", self.eye_color.as_ref().map(Field::to_string)),
+            ("pid", self.passport_id.as_ref().map(Field::to_string)),
+            ("cid", self.country_id.clone()),
+        ];
+        fields
+            .iter()
+            .filter_map(|(key, value)| value.as_ref().map(|v| format!("{}:{}", key, v)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Reads a batch file into typed passports, keeping values without the right shape as written.
+/// Unlike `parse`, every field must be one the puzzle knows; passports with others are set
+/// aside in `rejected` with the reason.  Blank records are skipped.
+pub fn parse_passports(input: &str) -> Result<Batch, ParseError> {
+    let mut batch = Batch::default();
+    for record in tokenize(input)?.iter().filter(|r| !r.is_empty()) {
+        match Passport::from_tokens(record) {
+            Ok(passport) => batch.passports.push(passport),
+            Err(e) => batch.rejected.push(e),
+        }
+    }
+    Ok(batch)
+}
+
+/// Writes passports in the batch format `parse_passports` reads, one line each.
+pub fn write_batch<W: io::Write>(mut writer: W, passports: &[Passport]) -> io::Result<()> {
+    for (i, passport) in passports.iter().enumerate() {
+        if i > 0 {
+            writeln!(writer)?;
+        }
+        writeln!(writer, "{}", passport.to_batch())?;
+    }
+    Ok(())
+}
+
+pub fn write_csv<W: io::Write>(writer: W, passports: &[Passport]) -> Result<(), csv::Error> {
+    let mut csv = csv::Writer::from_writer(writer);
+    for passport in passports {
+        csv.serialize(passport)?;
+    }
+    csv.flush()?;
+    Ok(())
+}
+
+pub fn write_json<W: io::Write>(mut writer: W, passports: &[Passport]) -> io::Result<()> {
+    for passport in passports {
+        serde_json::to_writer(&mut writer, passport)?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,312 @@
+use crate::Fields;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// The rules the puzzle sets out, embedded so the solver works without any files.
+pub const PUZZLE: &str = include_str!("../schema.toml");
+
+/// An inclusive range of whole numbers.
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Bounds {
+    pub min: i64,
+    pub max: i64,
+}
+
+impl Bounds {
+    fn check(&self, value: &str, matching: Matching) -> Result<(), Failure> {
+        let number = value.parse::<i64>().map_err(|_| Failure::NotANumber)?;
+        if matching == Matching::Strict && number.to_string() != value {
+            return Err(Failure::NotANumber);
+        }
+        if number < self.min || number > self.max {
+            return Err(Failure::OutOfRange);
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Bounds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.min, self.max)
+    }
+}
+
+/// Why a field failed its check.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Failure {
+    Missing,
+    NotANumber,
+    OutOfRange,
+    BadUnit,
+    RegexMismatch,
+    NotListed,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Failure::Missing => "missing",
+                Failure::NotANumber => "not a number",
+                Failure::OutOfRange => "out of range",
+                Failure::BadUnit => "bad unit",
+                Failure::RegexMismatch => "regex mismatch",
+                Failure::NotListed => "not listed",
+            }
+        )
+    }
+}
+
+/// How much of a value has to follow its rule.
+#[derive(Copy, Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Matching {
+    /// A pattern may match anywhere in the value and numbers may carry a sign or leading
+    /// zeros, as the puzzle solution used to allow.  Only used when asked for.
+    Lenient,
+    /// Patterns must match the whole value and numbers must be written plainly.
+    #[default]
+    Strict,
+}
+
+impl Matching {
+    pub fn parse(value: &str) -> Result<Matching, String> {
+        match value {
+            "lenient" => Ok(Matching::Lenient),
+            "strict" => Ok(Matching::Strict),
+            x => Err(format!("Unknown matching {}", x)),
+        }
+    }
+}
+
+/// A regular expression along with a copy anchored to both ends of the value.
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    anywhere: Regex,
+    whole: Regex,
+}
+
+impl Pattern {
+    fn new(pattern: &str) -> Result<Pattern, regex::Error> {
+        Ok(Pattern {
+            anywhere: Regex::new(pattern)?,
+            whole: Regex::new(&format!("^(?:{})$", pattern))?,
+        })
+    }
+
+    fn captures<'t>(&self, value: &'t str, matching: Matching) -> Option<Captures<'t>> {
+        match matching {
+            Matching::Lenient => self.anywhere.captures(value),
+            Matching::Strict => self.whole.captures(value),
+        }
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.anywhere)
+    }
+}
+
+/// A rule as written in a schema file.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+enum RuleFile {
+    Range(Bounds),
+    Units(BTreeMap<String, Bounds>),
+    Regex(String),
+    OneOf(Vec<String>),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SchemaFile {
+    #[serde(default)]
+    matching: Matching,
+    required: Vec<String>,
+    #[serde(default)]
+    optional: Vec<String>,
+    #[serde(default)]
+    rules: BTreeMap<String, RuleFile>,
+}
+
+/// What a field's value must look like.
+#[derive(Clone, Debug)]
+pub enum Rule {
+    /// A whole number within the bounds.
+    Range(Bounds),
+    /// A whole number followed by one of the units, within that unit's bounds.  `pattern` finds
+    /// the number and unit in the value.
+    Units {
+        units: BTreeMap<String, Bounds>,
+        pattern: Pattern,
+    },
+    /// Text matching the expression.
+    Regex(Pattern),
+    /// Exactly one of the listed values.
+    OneOf(Vec<String>),
+}
+
+impl Rule {
+    fn compile(field: &str, rule: RuleFile) -> Result<Rule, String> {
+        Ok(match rule {
+            RuleFile::Range(bounds) => Rule::Range(bounds),
+            RuleFile::Units(units) => {
+                if units.is_empty() {
+                    return Err(format!("The units rule for {} lists no units", field));
+                }
+                let alternatives = units
+                    .keys()
+                    .map(|u| regex::escape(u))
+                    .collect::<Vec<_>>()
+                    .join("|");
+                let pattern = Pattern::new(&format!("(?P<size>[0-9]+)(?P<unit>{})", alternatives))
+                    .map_err(|e| e.to_string())?;
+                Rule::Units { units, pattern }
+            }
+            RuleFile::Regex(pattern) => Rule::Regex(
+                Pattern::new(&pattern)
+                    .map_err(|e| format!("Invalid pattern for {}: {}", field, e))?,
+            ),
+            RuleFile::OneOf(values) => Rule::OneOf(values),
+        })
+    }
+
+    pub fn check(&self, value: &str, matching: Matching) -> Result<(), Failure> {
+        match self {
+            Rule::Range(bounds) => bounds.check(value, matching),
+            Rule::Units { units, pattern } => match pattern.captures(value, matching) {
+                Some(capture) => units[&capture["unit"]].check(&capture["size"], matching),
+                None => Err(Failure::BadUnit),
+            },
+            Rule::Regex(pattern) if pattern.captures(value, matching).is_some() => Ok(()),
+            Rule::Regex(_) => Err(Failure::RegexMismatch),
+            Rule::OneOf(values) if values.iter().any(|v| v == value) => Ok(()),
+            Rule::OneOf(_) => Err(Failure::NotListed),
+        }
+    }
+
+    pub fn accepts(&self, value: &str, matching: Matching) -> bool {
+        self.check(value, matching).is_ok()
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rule::Range(bounds) => write!(f, "{}", bounds),
+            Rule::Units { units, .. } => write!(
+                f,
+                "{}",
+                units
+                    .iter()
+                    .map(|(unit, bounds)| format!("{}{}", bounds, unit))
+                    .collect::<Vec<_>>()
+                    .join(" or ")
+            ),
+            Rule::Regex(regex) => write!(f, "matches {}", regex),
+            Rule::OneOf(values) => write!(f, "one of {}", values.join(" ")),
+        }
+    }
+}
+
+/// Which fields a passport needs and what their values must look like.
+#[derive(Clone, Debug)]
+pub struct Schema {
+    pub matching: Matching,
+    pub required: Vec<String>,
+    pub optional: Vec<String>,
+    pub rules: BTreeMap<String, Rule>,
+}
+
+impl Schema {
+    fn compile(file: SchemaFile) -> Result<Schema, String> {
+        if let Some(field) = file
+            .rules
+            .keys()
+            .find(|f| !file.required.contains(f) && !file.optional.contains(f))
+        {
+            return Err(format!(
+                "{} has a rule but is neither required nor optional",
+                field
+            ));
+        }
+
+        let rules = file
+            .rules
+            .into_iter()
+            .map(|(field, rule)| Ok((field.clone(), Rule::compile(&field, rule)?)))
+            .collect::<Result<_, String>>()?;
+
+        Ok(Schema {
+            matching: file.matching,
+            required: file.required,
+            optional: file.optional,
+            rules,
+        })
+    }
+
+    pub fn from_toml(text: &str) -> Result<Schema, String> {
+        Schema::compile(toml::from_str(text).map_err(|e| e.to_string())?)
+    }
+
+    pub fn from_json(text: &str) -> Result<Schema, String> {
+        Schema::compile(serde_json::from_str(text).map_err(|e| e.to_string())?)
+    }
+
+    /// Reads a schema file, as JSON if its name ends in `.json` and as TOML otherwise.
+    pub fn load(path: &Path) -> Result<Schema, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+        let schema = if path.extension().is_some_and(|e| e == "json") {
+            Schema::from_json(&text)
+        } else {
+            Schema::from_toml(&text)
+        };
+        schema.map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Every field the schema knows about, required ones first.
+    pub fn fields(&self) -> impl Iterator<Item = &String> {
+        self.required.iter().chain(self.optional.iter())
+    }
+
+    /// Checks one field of `passport`, failing when a required field is missing.
+    pub fn check(&self, passport: &Fields, field: &str) -> Result<(), Failure> {
+        match (passport.get(field), self.rules.get(field)) {
+            (Some(value), Some(rule)) => rule.check(value, self.matching),
+            (Some(_), None) => Ok(()),
+            (None, _) if self.required.iter().any(|f| f == field) => Err(Failure::Missing),
+            (None, _) => Ok(()),
+        }
+    }
+
+    /// Whether every required field is present.
+    pub fn has_fields(&self, passport: &Fields) -> bool {
+        self.required.iter().all(|f| passport.contains_key(f))
+    }
+
+    /// Whether every field with a rule holds a value it accepts, required fields being present.
+    pub fn is_valid(&self, passport: &Fields) -> bool {
+        self.rules
+            .iter()
+            .all(|(field, rule)| match passport.get(field) {
+                Some(value) => rule.accepts(value, self.matching),
+                None => !self.required.contains(field),
+            })
+    }
+}
+
+impl Default for Schema {
+    fn default() -> Self {
+        Schema::from_toml(PUZZLE).expect("the puzzle schema is valid")
+    }
+}
@@ -0,0 +1,208 @@
+use crate::schema::{Failure, Schema};
+use crate::Fields;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// How many passports carry a field.
+#[derive(Clone, Debug, Serialize)]
+pub struct Presence {
+    pub field: String,
+    pub present: usize,
+    pub rate: f64,
+}
+
+/// How many passports fail a field for one reason.
+#[derive(Clone, Debug, Serialize)]
+pub struct FailureCount {
+    pub field: String,
+    pub failure: Failure,
+    pub passports: usize,
+}
+
+/// A summary of a batch for spotting anything odd about it.
+#[derive(Clone, Debug, Serialize)]
+pub struct Statistics {
+    pub passports: usize,
+    pub valid: usize,
+    pub presence: Vec<Presence>,
+    pub eye_colors: BTreeMap<String, usize>,
+    pub height_units: BTreeMap<String, usize>,
+    pub birth_years: BTreeMap<String, usize>,
+    /// Most common first.
+    pub failures: Vec<FailureCount>,
+    /// Fields the schema doesn't know about, with how many passports carry each.
+    pub unknown_fields: BTreeMap<String, usize>,
+}
+
+/// The unit a height is given in: whatever follows its leading digits.
+fn height_unit(value: &str) -> String {
+    match value.trim_start_matches(|c: char| c.is_ascii_digit()) {
+        "" => "(none)".to_string(),
+        unit => unit.to_string(),
+    }
+}
+
+/// The decade a birth year falls in, e.g. `1950-1959`.
+fn birth_decade(value: &str) -> String {
+    match value.parse::<i64>() {
+        Ok(year) => {
+            let decade = year.div_euclid(10) * 10;
+            format!("{}-{}", decade, decade + 9)
+        }
+        Err(_) => "(not a number)".to_string(),
+    }
+}
+
+pub fn analyze(passports: &[Fields], schema: &Schema) -> Statistics {
+    let histogram = |field: &str, bucket: &dyn Fn(&str) -> String| {
+        let mut counts = BTreeMap::new();
+        for value in passports.iter().filter_map(|p| p.get(field)) {
+            *counts.entry(bucket(value)).or_insert(0) += 1;
+        }
+        counts
+    };
+    let eye_colors = histogram("ecl", &|v| v.to_string());
+    let height_units = histogram("hgt", &height_unit);
+    let birth_years = histogram("byr", &birth_decade);
+
+    let presence = schema
+        .fields()
+        .map(|field| {
+            let present = passports.iter().filter(|p| p.contains_key(field)).count();
+            Presence {
+                field: field.clone(),
+                present,
+                rate: if passports.is_empty() {
+                    0.0
+                } else {
+                    present as f64 / passports.len() as f64
+                },
+            }
+        })
+        .collect();
+
+    let mut failures = HashMap::new();
+    let mut valid = 0;
+    for passport in passports {
+        let mut passed = true;
+        for field in schema.fields() {
+            if let Err(failure) = schema.check(passport, field) {
+                *failures.entry((field.clone(), failure)).or_insert(0) += 1;
+                passed = false;
+            }
+        }
+        if passed {
+            valid += 1;
+        }
+    }
+    let mut failures = failures
+        .into_iter()
+        .map(|((field, failure), passports)| FailureCount {
+            field,
+            failure,
+            passports,
+        })
+        .collect::<Vec<_>>();
+    failures.sort_by(|a, b| {
+        b.passports
+            .cmp(&a.passports)
+            .then_with(|| (&a.field, a.failure).cmp(&(&b.field, b.failure)))
+    });
+
+    let mut unknown_fields = BTreeMap::new();
+    for field in passports.iter().flat_map(|p| p.keys()) {
+        if !schema.fields().any(|f| f == field) {
+            *unknown_fields.entry(field.clone()).or_insert(0) += 1;
+        }
+    }
+
+    Statistics {
+        passports: passports.len(),
+        valid,
+        presence,
+        eye_colors,
+        height_units,
+        birth_years,
+        failures,
+        unknown_fields,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, valid};
+
+    const BATCH: &str = "byr:1980 iyr:2015 eyr:2025 hgt:180cm hcl:#123abc ecl:brn pid:000000001
+
+byr:1975 iyr:2012 eyr:2022 hgt:70in hcl:#abcdef ecl:brn pid:000000002 cid:1
+
+byr:2003 iyr:2015 eyr:2025 hgt:180 hcl:#123abc ecl:xyz pid:000000003 foo:bar
+
+hgt:190 ecl:brn
+";
+
+    fn statistics() -> Statistics {
+        analyze(&parse(BATCH).unwrap(), &Schema::default())
+    }
+
+    fn counts(pairs: &[(&str, usize)]) -> BTreeMap<String, usize> {
+        pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn valid_agrees_with_the_schema() {
+        let passports = parse(BATCH).unwrap();
+        let statistics = statistics();
+        assert_eq!(statistics.passports, 4);
+        assert_eq!(statistics.valid, 2);
+        assert_eq!(statistics.valid, valid(&passports, &Schema::default()));
+    }
+
+    #[test]
+    fn histograms_bucket_each_value() {
+        let statistics = statistics();
+        assert_eq!(statistics.eye_colors, counts(&[("brn", 3), ("xyz", 1)]));
+        assert_eq!(
+            statistics.height_units,
+            counts(&[("(none)", 2), ("cm", 1), ("in", 1)])
+        );
+        assert_eq!(
+            statistics.birth_years,
+            counts(&[("1970-1979", 1), ("1980-1989", 1), ("2000-2009", 1)])
+        );
+        assert_eq!(statistics.unknown_fields, counts(&[("foo", 1)]));
+    }
+
+    #[test]
+    fn presence_follows_the_schema_fields() {
+        let statistics = statistics();
+        let byr = &statistics.presence[0];
+        assert_eq!((byr.field.as_str(), byr.present), ("byr", 3));
+        assert!((byr.rate - 0.75).abs() < 1e-9);
+        let cid = statistics.presence.last().unwrap();
+        assert_eq!((cid.field.as_str(), cid.present), ("cid", 1));
+    }
+
+    #[test]
+    fn failures_are_most_common_first() {
+        let statistics = statistics();
+        let failures = statistics
+            .failures
+            .iter()
+            .map(|f| (f.field.as_str(), f.failure, f.passports))
+            .collect::<Vec<_>>();
+        assert_eq!(failures[0], ("hgt", Failure::BadUnit, 2));
+        assert!(failures.windows(2).all(|w| w[0].2 >= w[1].2));
+        assert!(failures.contains(&("byr", Failure::OutOfRange, 1)));
+        assert!(failures.contains(&("byr", Failure::Missing, 1)));
+        assert!(failures.contains(&("ecl", Failure::NotListed, 1)));
+    }
+
+    #[test]
+    fn empty_batches_have_no_rates() {
+        let statistics = analyze(&[], &Schema::default());
+        assert_eq!(statistics.passports, 0);
+        assert!(statistics.presence.iter().all(|p| p.rate == 0.0));
+    }
+}
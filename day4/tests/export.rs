@@ -0,0 +1,103 @@
+use day4::parse;
+use day4::passport::{self, Field, Passport};
+
+const PUZZLE: &str = include_str!("../../Data/day4.txt");
+
+/// One field of each kind that doesn't have its field's shape.
+const INVALID: &str = "byr:19x0 iyr:2010 eyr:0042 hgt:190 hcl:123abc ecl:zzz pid:0123456789 cid:7
+
+hgt:59cm ecl:amb pid:12345";
+
+fn passports(input: &str) -> Vec<Passport> {
+    let batch = passport::parse_passports(input).unwrap();
+    assert!(batch.rejected.is_empty(), "{:?}", batch.rejected);
+    batch.passports
+}
+
+/// Writes `passports` as a batch file and types it again.
+fn through_batch(passports: &[Passport]) -> Vec<Passport> {
+    let mut batch = vec![];
+    passport::write_batch(&mut batch, passports).unwrap();
+    self::passports(&String::from_utf8(batch).unwrap())
+}
+
+fn through_json(passports: &[Passport]) -> Vec<Passport> {
+    let mut json = vec![];
+    passport::write_json(&mut json, passports).unwrap();
+    String::from_utf8(json)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str::<Passport>(l).unwrap())
+        .collect()
+}
+
+fn through_csv(passports: &[Passport]) -> Vec<Passport> {
+    let mut csv = vec![];
+    passport::write_csv(&mut csv, passports).unwrap();
+    csv::Reader::from_reader(csv.as_slice())
+        .deserialize::<Passport>()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+}
+
+#[test]
+fn every_record_is_kept() {
+    let passports = passports(PUZZLE);
+    assert_eq!(passports.len(), parse(PUZZLE).unwrap().len());
+    assert!(passports.iter().any(|p| p.birth_year.is_none()));
+    assert!(passports
+        .iter()
+        .any(|p| matches!(p.height, Some(Field::Other(_)))));
+}
+
+fn other<T>(value: &str) -> Option<Field<T>> {
+    Some(Field::Other(value.to_string()))
+}
+
+#[test]
+fn values_without_the_right_shape_are_kept_as_written() {
+    let passports = passports(INVALID);
+    assert_eq!(passports[0].birth_year, other("19x0"));
+    assert_eq!(passports[0].issue_year, Some(Field::Valid(2010)));
+    assert_eq!(passports[0].height, other("190"));
+    assert_eq!(passports[0].hair_color, other("123abc"));
+    assert_eq!(passports[0].eye_color, other("zzz"));
+    assert_eq!(passports[0].passport_id, other("0123456789"));
+    assert_eq!(passports[1].passport_id, other("12345"));
+    assert!(passports[1].height.as_ref().unwrap().valid().is_some());
+}
+
+#[test]
+fn fields_the_puzzle_does_not_know_are_rejected() {
+    let batch = passport::parse_passports("byr:1990 xyz:1\n\nbyr:1991").unwrap();
+    assert_eq!(batch.passports.len(), 1);
+    assert_eq!(batch.rejected.len(), 1);
+}
+
+#[test]
+fn batches_round_trip() {
+    for input in [PUZZLE, INVALID].iter() {
+        let passports = passports(input);
+        assert_eq!(through_batch(&passports), passports);
+    }
+}
+
+#[test]
+fn json_round_trips_to_the_batch() {
+    for input in [PUZZLE, INVALID].iter() {
+        let passports = passports(input);
+        let read = through_json(&passports);
+        assert_eq!(read, passports);
+        assert_eq!(through_batch(&read), passports);
+    }
+}
+
+#[test]
+fn csv_round_trips_to_the_batch() {
+    for input in [PUZZLE, INVALID].iter() {
+        let passports = passports(input);
+        let read = through_csv(&passports);
+        assert_eq!(read, passports);
+        assert_eq!(through_batch(&read), passports);
+    }
+}
@@ -0,0 +1,180 @@
+use day4::schema::{Failure, Matching, Schema};
+use day4::{parse, valid};
+
+const VALID: &str = include_str!("../../Data/day4.part2.valid.txt");
+const INVALID: &str = include_str!("../../Data/day4.part2.invalid.txt");
+
+fn lenient() -> Schema {
+    Schema {
+        matching: Matching::Lenient,
+        ..Schema::default()
+    }
+}
+
+/// Checks `value` as the `field` of an otherwise empty passport under both matchings.
+fn check(field: &str, value: &str) -> (Result<(), Failure>, Result<(), Failure>) {
+    let passport = parse(&format!("{}:{}", field, value)).unwrap().remove(0);
+    (
+        lenient().check(&passport, field),
+        Schema::default().check(&passport, field),
+    )
+}
+
+fn accepted(field: &str, values: &[&str]) {
+    for value in values {
+        assert_eq!(check(field, value), (Ok(()), Ok(())), "{}:{}", field, value);
+    }
+}
+
+fn rejected(field: &str, values: &[(&str, Failure)]) {
+    for (value, failure) in values {
+        assert_eq!(
+            check(field, value),
+            (Err(*failure), Err(*failure)),
+            "{}:{}",
+            field,
+            value
+        );
+    }
+}
+
+/// Values only the default, strict matching catches.
+fn loose(field: &str, values: &[(&str, Failure)]) {
+    for (value, failure) in values {
+        assert_eq!(
+            check(field, value),
+            (Ok(()), Err(*failure)),
+            "{}:{}",
+            field,
+            value
+        );
+    }
+}
+
+#[test]
+fn the_puzzle_schema_is_strict() {
+    assert_eq!(Schema::default().matching, Matching::Strict);
+}
+
+#[test]
+fn example_batches_agree_under_both_matchings() {
+    for schema in [lenient(), Schema::default()].iter() {
+        let passports = parse(VALID).unwrap();
+        assert_eq!(valid(&passports, schema), passports.len());
+        assert_eq!(valid(&parse(INVALID).unwrap(), schema), 0);
+    }
+}
+
+#[test]
+fn years_are_plain_numbers_in_range() {
+    accepted("byr", &["1920", "2002"]);
+    accepted("iyr", &["2010", "2020"]);
+    accepted("eyr", &["2020", "2030"]);
+    rejected(
+        "byr",
+        &[
+            ("1919", Failure::OutOfRange),
+            ("2003", Failure::OutOfRange),
+            ("abcd", Failure::NotANumber),
+            ("", Failure::NotANumber),
+            ("19a0", Failure::NotANumber),
+            ("99999999999999999999", Failure::NotANumber),
+        ],
+    );
+    loose(
+        "byr",
+        &[
+            ("+2000", Failure::NotANumber),
+            ("02000", Failure::NotANumber),
+        ],
+    );
+}
+
+#[test]
+fn heights_need_a_unit_and_a_size_in_its_range() {
+    accepted("hgt", &["150cm", "193cm", "59in", "76in"]);
+    rejected(
+        "hgt",
+        &[
+            ("149cm", Failure::OutOfRange),
+            ("194cm", Failure::OutOfRange),
+            ("58in", Failure::OutOfRange),
+            ("77in", Failure::OutOfRange),
+            ("190", Failure::BadUnit),
+            ("190mm", Failure::BadUnit),
+            ("cm", Failure::BadUnit),
+        ],
+    );
+    loose(
+        "hgt",
+        &[
+            ("170cmx", Failure::BadUnit),
+            ("x170cm", Failure::BadUnit),
+            ("60in60", Failure::BadUnit),
+            ("0170cm", Failure::NotANumber),
+        ],
+    );
+}
+
+#[test]
+fn hair_colors_are_six_lowercase_hex_digits() {
+    accepted("hcl", &["#123abc", "#000000", "#ffffff"]);
+    rejected(
+        "hcl",
+        &[
+            ("#123abz", Failure::RegexMismatch),
+            ("123abc", Failure::RegexMismatch),
+            ("#123AB", Failure::RegexMismatch),
+            ("#12345", Failure::RegexMismatch),
+        ],
+    );
+    loose(
+        "hcl",
+        &[
+            ("#123abcz", Failure::RegexMismatch),
+            ("#1234567", Failure::RegexMismatch),
+            ("x#123abc", Failure::RegexMismatch),
+        ],
+    );
+}
+
+#[test]
+fn eye_colors_come_from_the_list() {
+    accepted("ecl", &["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]);
+    rejected(
+        "ecl",
+        &[
+            ("wat", Failure::NotListed),
+            ("brnx", Failure::NotListed),
+            ("BRN", Failure::NotListed),
+            ("", Failure::NotListed),
+        ],
+    );
+}
+
+#[test]
+fn passport_ids_are_exactly_nine_digits() {
+    accepted("pid", &["000000001", "012533040"]);
+    rejected(
+        "pid",
+        &[
+            ("12345678", Failure::RegexMismatch),
+            ("12345678a", Failure::RegexMismatch),
+        ],
+    );
+    loose(
+        "pid",
+        &[
+            ("0123456789", Failure::RegexMismatch),
+            ("a123456789", Failure::RegexMismatch),
+        ],
+    );
+}
+
+#[test]
+fn missing_fields_fail_unless_optional() {
+    let passport = parse("byr:1980").unwrap().remove(0);
+    let schema = Schema::default();
+    assert_eq!(schema.check(&passport, "pid"), Err(Failure::Missing));
+    assert_eq!(schema.check(&passport, "cid"), Ok(()));
+}
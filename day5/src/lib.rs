@@ -0,0 +1,153 @@
+use common::error;
+use common::{Answer, Day, Error, Example, Input, ParseError, Part};
+use std::fmt;
+
+/// Letters in a boarding pass for the row and for the column.
+const ROW_LETTERS: usize = 7;
+const COLUMN_LETTERS: usize = 3;
+
+/// Seat IDs run from 0 up to but not including this.
+pub const SEATS: i32 = 1 << (ROW_LETTERS + COLUMN_LETTERS);
+
+/// The seat a boarding pass decodes to.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Assignment {
+    pub row: i32,
+    pub column: i32,
+}
+
+impl Assignment {
+    pub fn id(&self) -> i32 {
+        self.row * 8 + self.column
+    }
+
+    /// The seat with the given ID, if there is one.
+    pub fn from_id(id: i32) -> Option<Assignment> {
+        if !(0..SEATS).contains(&id) {
+            return None;
+        }
+        Some(Assignment {
+            row: id >> COLUMN_LETTERS,
+            column: id & ((1 << COLUMN_LETTERS) - 1),
+        })
+    }
+
+    /// The boarding pass for this seat, or `None` if the row or column is off the plane.
+    pub fn pass(&self) -> Option<String> {
+        if !(0..1 << ROW_LETTERS).contains(&self.row)
+            || !(0..1 << COLUMN_LETTERS).contains(&self.column)
+        {
+            return None;
+        }
+        Some(
+            encode(self.row, ROW_LETTERS, 'F', 'B')
+                + &encode(self.column, COLUMN_LETTERS, 'L', 'R'),
+        )
+    }
+}
+
+/// Spells out the low `letters` bits of `value`, most significant first.
+fn encode(value: i32, letters: usize, zero: char, one: char) -> String {
+    (0..letters)
+        .rev()
+        .map(|bit| if value >> bit & 1 == 1 { one } else { zero })
+        .collect()
+}
+
+/// Reads letters as binary digits, `B` and `R` being ones and `F` and `L` zeros.
+fn decode(letters: &str) -> i32 {
+    letters
+        .bytes()
+        .fold(0, |value, b| value << 1 | matches!(b, b'B' | b'R') as i32)
+}
+
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "row {0}, column {1}, seat ID {2}",
+            self.row,
+            self.column,
+            self.id()
+        )
+    }
+}
+
+pub fn parse_assignment(line: &str) -> Result<Assignment, ParseError> {
+    for (i, c) in line.char_indices() {
+        let valid = match i {
+            0..=6 => c == 'F' || c == 'B',
+            7..=9 => c == 'L' || c == 'R',
+            _ => false,
+        };
+        if !valid {
+            return Err(ParseError::at(
+                line,
+                &line[i..i + c.len_utf8()],
+                match i {
+                    0..=6 => "F or B for the row",
+                    7..=9 => "L or R for the column",
+                    _ => "the pass to end after 10 letters",
+                },
+            ));
+        }
+    }
+
+    if line.len() < 10 {
+        return Err(ParseError::new(
+            line.len() + 1,
+            line,
+            "10 letters such as FBFBBFFRLR",
+        ));
+    }
+
+    Ok(Assignment {
+        row: decode(&line[..ROW_LETTERS]),
+        column: decode(&line[ROW_LETTERS..]),
+    })
+}
+
+pub fn parse(input: &str) -> Result<Vec<Assignment>, ParseError> {
+    error::parse_lines(input, parse_assignment)
+}
+
+/// The highest seat ID on a boarding pass.
+pub fn part1(assignments: &[Assignment]) -> Option<i32> {
+    assignments.iter().map(|a| a.id()).max()
+}
+
+/// The missing seat ID whose neighbours are both taken.
+pub fn part2(assignments: &[Assignment]) -> Option<i32> {
+    let mut ids = assignments.iter().map(|a| a.id()).collect::<Vec<_>>();
+    ids.sort_unstable_by_key(|id| std::cmp::Reverse(*id));
+
+    // find the hole
+    ids.windows(2)
+        .find(|pair| pair[1] != pair[0] - 1)
+        .map(|pair| pair[0] - 1)
+}
+
+fn solve_part1(input: &str) -> Result<Answer, Error> {
+    part1(&parse(input)?)
+        .map(|id| id as Answer)
+        .ok_or_else(|| Error::NoSolution("no boarding passes".to_string()))
+}
+
+fn solve_part2(input: &str) -> Result<Answer, Error> {
+    part2(&parse(input)?)
+        .map(|id| id as Answer)
+        .ok_or_else(|| Error::NoSolution("no vacant seat".to_string()))
+}
+
+pub static DAY: Day = Day {
+    number: 5,
+    title: "Binary Boarding",
+    part1: Some(solve_part1),
+    part2: Some(solve_part2),
+    examples: &[Example {
+        part: Part::One,
+        input: Input::Example(1),
+        expected: 820,
+        solver: None,
+    }],
+};
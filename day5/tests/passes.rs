@@ -0,0 +1,84 @@
+use day5::{parse_assignment, Assignment, SEATS};
+
+#[test]
+fn puzzle_examples_decode() {
+    let examples = [
+        ("FBFBBFFRLR", 44, 5, 357),
+        ("BFFFBBFRRR", 70, 7, 567),
+        ("FFFBBBFRRR", 14, 7, 119),
+        ("BBFFBBFRLL", 102, 4, 820),
+    ];
+    for (pass, row, column, id) in examples.iter() {
+        let seat = parse_assignment(pass).unwrap();
+        assert_eq!((seat.row, seat.column, seat.id()), (*row, *column, *id));
+        assert_eq!(seat.pass().as_deref(), Some(*pass));
+    }
+}
+
+#[test]
+fn every_seat_id_round_trips() {
+    for id in 0..SEATS {
+        let seat = Assignment::from_id(id).unwrap();
+        assert_eq!(seat.id(), id);
+
+        let pass = seat.pass().unwrap();
+        assert_eq!(pass.len(), 10);
+        assert_eq!(parse_assignment(&pass).unwrap(), seat, "{}", pass);
+    }
+}
+
+#[test]
+fn every_row_and_column_round_trips() {
+    for row in 0..128 {
+        for column in 0..8 {
+            let seat = Assignment { row, column };
+            let decoded = parse_assignment(&seat.pass().unwrap()).unwrap();
+            assert_eq!(decoded, seat);
+            assert_eq!(Assignment::from_id(seat.id()), Some(seat));
+        }
+    }
+}
+
+#[test]
+fn passes_are_distinct_and_ordered_like_their_ids() {
+    let passes = (0..SEATS)
+        .map(|id| Assignment::from_id(id).unwrap().pass().unwrap())
+        .collect::<Vec<_>>();
+    let mut sorted = passes.clone();
+    // F and L sort before B and R, just as 0 does before 1.
+    sorted.sort_by_key(|p| p.replace(['F', 'L'], "0"));
+    sorted.dedup();
+    assert_eq!(sorted, passes);
+}
+
+#[test]
+fn seats_off_the_plane_have_no_pass() {
+    assert_eq!(Assignment::from_id(-1), None);
+    assert_eq!(Assignment::from_id(SEATS), None);
+    assert_eq!(
+        Assignment {
+            row: 128,
+            column: 0
+        }
+        .pass(),
+        None
+    );
+    assert_eq!(Assignment { row: 0, column: 8 }.pass(), None);
+    assert_eq!(Assignment { row: -1, column: 0 }.pass(), None);
+}
+
+#[test]
+fn malformed_passes_are_rejected() {
+    for pass in [
+        "",
+        "FBFBBFFRL",
+        "FBFBBFFRLRR",
+        "FBFBBFFRLX",
+        "LBFBBFFRLR",
+        "FBFBBFFFLR",
+    ]
+    .iter()
+    {
+        assert!(parse_assignment(pass).is_err(), "{}", pass);
+    }
+}
@@ -0,0 +1,126 @@
+use common::{Answer, Day, Error, Example, Input, ParseError, Part};
+use std::collections::HashMap;
+
+/// A group's declarations: how many members answered yes to each question.
+#[derive(Clone, Debug)]
+pub struct CustomsForm {
+    pub claims: HashMap<char, i32>,
+    pub members: i32,
+}
+
+/// Questions anyone in each group answered yes to, summed across groups.
+pub fn count_individual_affirmatives(responses: &[CustomsForm]) -> i32 {
+    responses.iter().map(|f| f.claims.len() as i32).sum::<i32>()
+}
+
+/// Questions everyone in each group answered yes to, summed across groups.
+pub fn count_group_affirmatives(responses: &[CustomsForm]) -> i32 {
+    responses
+        .iter()
+        .map(|f| {
+            f.claims
+                .values()
+                .map(|count| match *count == f.members {
+                    true => 1,
+                    _ => 0,
+                })
+                .sum::<i32>()
+        })
+        .sum()
+}
+
+fn condense(answers: &[&str]) -> CustomsForm {
+    let mut claims: HashMap<char, i32> = HashMap::new();
+
+    for c in answers.iter().flat_map(|a| a.chars()) {
+        let count = claims.entry(c).or_insert(0);
+        *count += 1;
+    }
+
+    CustomsForm {
+        claims,
+        members: answers.len() as i32,
+    }
+}
+
+fn parse_answers(line: &str) -> Result<&str, ParseError> {
+    match line.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+        Some((i, c)) => Err(ParseError::at(
+            line,
+            &line[i..i + c.len_utf8()],
+            "a question letter from a to z",
+        )),
+        None => Ok(line),
+    }
+}
+
+/// Reads the groups, which are separated by blank lines.
+pub fn parse(input: &str) -> Result<Vec<CustomsForm>, ParseError> {
+    let mut forms = vec![];
+    let mut group = vec![];
+
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            if !group.is_empty() {
+                forms.push(condense(&group));
+                group.clear();
+            }
+        } else {
+            group.push(parse_answers(line).map_err(|e| e.on_line(i + 1))?);
+        }
+    }
+    if !group.is_empty() {
+        forms.push(condense(&group));
+    }
+
+    Ok(forms)
+}
+
+pub fn part1(forms: &[CustomsForm]) -> i32 {
+    count_individual_affirmatives(forms)
+}
+
+pub fn part2(forms: &[CustomsForm]) -> i32 {
+    count_group_affirmatives(forms)
+}
+
+fn solve_part1(input: &str) -> Result<Answer, Error> {
+    Ok(part1(&parse(input)?) as Answer)
+}
+
+fn solve_part2(input: &str) -> Result<Answer, Error> {
+    Ok(part2(&parse(input)?) as Answer)
+}
+
+pub static DAY: Day = Day {
+    number: 6,
+    title: "Custom Customs",
+    part1: Some(solve_part1),
+    part2: Some(solve_part2),
+    examples: &[
+        Example {
+            part: Part::One,
+            input: Input::Example(1),
+            expected: 6,
+            solver: None,
+        },
+        Example {
+            part: Part::One,
+            input: Input::Example(2),
+            expected: 11,
+            solver: None,
+        },
+        Example {
+            part: Part::Two,
+            input: Input::Example(1),
+            expected: 3,
+            solver: None,
+        },
+        Example {
+            part: Part::Two,
+            input: Input::Example(2),
+            expected: 6,
+            solver: None,
+        },
+    ],
+};
@@ -0,0 +1,133 @@
+use common::error;
+use common::{Answer, Day, Error, Example, Input, ParseError, Part};
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// Each bag color mapped to the colors and counts of the bags it must directly contain.
+pub type Rules = HashMap<String, HashMap<String, i32>>;
+
+const TARGET: &str = "shiny gold";
+
+fn parse_contents(line: &str, s: &str) -> Result<HashMap<String, i32>, ParseError> {
+    if s.starts_with("no") {
+        Ok(HashMap::new())
+    } else {
+        s.split(", ")
+            .map(|c| {
+                let mut parts = c.trim().split(' ');
+                let count = parts
+                    .next()
+                    .and_then(|n| n.parse::<i32>().ok())
+                    .ok_or_else(|| ParseError::at(line, c, "a count such as 2 shiny gold bags"))?;
+                let name = match (parts.next(), parts.next()) {
+                    (Some(adjective), Some(color)) => format!("{} {}", adjective, color),
+                    _ => {
+                        return Err(ParseError::at(
+                            line,
+                            c,
+                            "a two word color such as shiny gold",
+                        ))
+                    }
+                };
+                Ok((name, count))
+            })
+            .collect()
+    }
+}
+
+pub fn decompose(s: &str) -> Result<(String, HashMap<String, i32>), ParseError> {
+    let mut parts = s.split("bags contain");
+    let color = parts.next().unwrap_or_default().trim().to_string();
+    let contents = parts
+        .next()
+        .ok_or_else(|| ParseError::new(1, s, "a rule such as light red bags contain ..."))?;
+
+    Ok((color, parse_contents(s, contents.trim())?))
+}
+
+pub fn parse(input: &str) -> Result<Rules, ParseError> {
+    Ok(error::parse_lines(input, decompose)?.into_iter().collect())
+}
+
+fn find_parent(rules: &Rules, color: &str) -> Vec<String> {
+    rules
+        .iter()
+        .filter(|(_, v)| v.contains_key(color))
+        .map(|(k, _)| k.clone())
+        .collect()
+}
+
+// replace with fold
+/// How many colors of bag can eventually hold a `color` bag.
+pub fn can_contain(rules: &Rules, color: &str) -> i32 {
+    let mut inspectable = Some(find_parent(rules, color));
+
+    let mut candidates: HashSet<String> = HashSet::new();
+
+    while let Some(parents) = inspectable {
+        let mut next = Vec::<String>::new();
+
+        for parent in parents {
+            candidates.insert(parent.clone());
+            for grandparent in find_parent(rules, &parent) {
+                next.push(grandparent);
+            }
+        }
+
+        match next.len() {
+            0 => inspectable = None,
+            _ => inspectable = Some(next),
+        }
+    }
+
+    candidates.len() as i32
+}
+
+/// How many bags a `color` bag holds in total, failing on the first color inside without a
+/// rule.
+pub fn count_children(rules: &Rules, color: &str) -> Result<i32, Error> {
+    let children = rules
+        .get(color)
+        .ok_or_else(|| Error::NoSolution(format!("there is no rule for {} bags", color)))?;
+    children
+        .iter()
+        .map(|(color, count)| Ok(count + count * count_children(rules, color)?))
+        .sum()
+}
+
+pub fn part1(rules: &Rules) -> i32 {
+    can_contain(rules, TARGET)
+}
+
+pub fn part2(rules: &Rules) -> Result<i32, Error> {
+    count_children(rules, TARGET)
+}
+
+fn solve_part1(input: &str) -> Result<Answer, Error> {
+    Ok(part1(&parse(input)?) as Answer)
+}
+
+fn solve_part2(input: &str) -> Result<Answer, Error> {
+    Ok(part2(&parse(input)?)? as Answer)
+}
+
+pub static DAY: Day = Day {
+    number: 7,
+    title: "Handy Haversacks",
+    part1: Some(solve_part1),
+    part2: Some(solve_part2),
+    examples: &[
+        Example {
+            part: Part::One,
+            input: Input::Example(1),
+            expected: 4,
+            solver: None,
+        },
+        Example {
+            part: Part::Two,
+            input: Input::Example(2),
+            expected: 126,
+            solver: None,
+        },
+    ],
+};
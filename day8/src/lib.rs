@@ -0,0 +1,231 @@
+use common::error;
+use common::{Answer, Day, Error, Example, Input, ParseError, Part};
+use std::collections::HashSet;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    pub operand: String,
+    pub positive: bool,
+    pub offset: i32,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum State {
+    Uninitalized,
+    Running,
+    Errored,
+    Completed,
+    /// A jump landed outside the program other than just past its last instruction.
+    OutOfBounds,
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {:5}",
+            self.operand,
+            if self.positive { "+" } else { "-" },
+            self.offset
+        )
+    }
+}
+
+/// Executes boot code one instruction at a time, stopping before any instruction would run
+/// a second time.
+pub struct Processor {
+    accumulator: i32,
+    current: i32,
+    instructions: Vec<Instruction>,
+    processed: HashSet<i32>,
+    state: State,
+}
+
+impl Processor {
+    pub fn new(instructions: Vec<Instruction>) -> Self {
+        Processor {
+            accumulator: 0,
+            current: 0,
+            processed: HashSet::new(),
+            state: State::Uninitalized,
+            instructions,
+        }
+    }
+
+    pub fn accumulator(&self) -> i32 {
+        self.accumulator
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn run(&mut self) -> (State, i32) {
+        while self.next().is_some() {}
+        (self.state, self.accumulator)
+    }
+}
+
+/// Steps through the program, yielding the address of each instruction as it executes.
+impl Iterator for Processor {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<i32> {
+        let len = self.instructions.len() as i32;
+        if self.current < 0 || self.current > len {
+            self.state = State::OutOfBounds;
+            None
+        } else if self.current < len {
+            if self.processed.contains(&self.current) {
+                self.state = State::Errored;
+                None
+            } else {
+                self.state = State::Running;
+                self.processed.insert(self.current);
+                let ip = Some(self.current);
+                let instruction = self.instructions[self.current as usize].clone();
+                self.current += match instruction.operand.as_str() {
+                    "acc" => {
+                        self.accumulator +=
+                            instruction.offset * if instruction.positive { 1 } else { -1 };
+                        1
+                    }
+                    "jmp" => instruction.offset * if instruction.positive { 1 } else { -1 },
+                    _ => 1,
+                };
+                ip
+            }
+        } else {
+            self.state = State::Completed;
+            None
+        }
+    }
+}
+
+impl fmt::Display for Processor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{0}", self.accumulator)
+    }
+}
+
+pub fn parse_instruction(raw: &str) -> Result<Instruction, ParseError> {
+    let (operand, argument) = raw
+        .split_once(' ')
+        .ok_or_else(|| ParseError::new(1, raw, "an instruction such as acc +1"))?;
+    if !matches!(operand, "acc" | "jmp" | "nop") {
+        return Err(ParseError::at(raw, operand, "acc, jmp or nop"));
+    }
+
+    let (positive, digits) = match (argument.strip_prefix('+'), argument.strip_prefix('-')) {
+        (Some(digits), _) => (true, digits),
+        (_, Some(digits)) => (false, digits),
+        _ => {
+            return Err(ParseError::at(
+                raw,
+                argument,
+                "a signed offset such as +4 or -20",
+            ))
+        }
+    };
+    let offset = match digits.chars().all(|c| c.is_ascii_digit()) {
+        true => digits.parse::<i32>().ok(),
+        false => None,
+    }
+    .ok_or_else(|| ParseError::at(raw, digits, "the offset digits"))?;
+
+    Ok(Instruction {
+        operand: operand.to_string(),
+        positive,
+        offset,
+    })
+}
+
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    error::parse_lines(input, parse_instruction)
+}
+
+/// The accumulator just before an instruction would run a second time, or why the program
+/// never got that far.
+pub fn part1(code: &[Instruction]) -> Result<i32, Error> {
+    let mut processor = Processor::new(code.to_vec());
+    match processor.run() {
+        (State::OutOfBounds, _) => Err(Error::NoSolution(format!(
+            "the program jumped to instruction {} outside 0..{}",
+            processor.current,
+            code.len()
+        ))),
+        (State::Completed, _) => Err(Error::NoSolution(
+            "the program terminated without repeating an instruction".to_string(),
+        )),
+        (_, accumulator) => Ok(accumulator),
+    }
+}
+
+/// The accumulator once the program terminates after swapping a single jmp or nop.
+pub fn part2(code: &[Instruction]) -> Option<i32> {
+    let mut current: usize = 0;
+    let mut value: Option<i32> = None;
+    let count = code.len();
+
+    while value.is_none() && current < count {
+        let mut new_code = code.to_vec();
+        value = match new_code[current].operand.as_str() {
+            "jmp" => {
+                new_code[current].operand = "nop".to_string();
+                let mut processor = Processor::new(new_code);
+                let result = processor.run();
+
+                match result.0 {
+                    State::Completed => Some(result.1),
+                    _ => None,
+                }
+            }
+            "nop" => {
+                new_code[current].operand = "jmp".to_string();
+                let mut processor = Processor::new(new_code);
+                let result = processor.run();
+
+                match result.0 {
+                    State::Completed => Some(result.1),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        current += 1;
+    }
+
+    value
+}
+
+fn solve_part1(input: &str) -> Result<Answer, Error> {
+    Ok(part1(&parse(input)?)? as Answer)
+}
+
+fn solve_part2(input: &str) -> Result<Answer, Error> {
+    part2(&parse(input)?)
+        .map(|x| x as Answer)
+        .ok_or_else(|| Error::NoSolution("no single patch lets the program terminate".to_string()))
+}
+
+pub static DAY: Day = Day {
+    number: 8,
+    title: "Handheld Halting",
+    part1: Some(solve_part1),
+    part2: Some(solve_part2),
+    examples: &[
+        Example {
+            part: Part::One,
+            input: Input::Example(1),
+            expected: 5,
+            solver: None,
+        },
+        Example {
+            part: Part::Two,
+            input: Input::Example(1),
+            expected: 8,
+            solver: None,
+        },
+    ],
+};
@@ -0,0 +1,167 @@
+use common::error;
+use common::{Answer, Day, Error, Example, Input, ParseError, Part};
+
+/// Checks XMAS data, where each number must be the sum of two of the `window` before it.
+pub struct XmasCracker {
+    preamble: i32,
+    window: i32,
+    bytes: Vec<i64>,
+}
+
+impl XmasCracker {
+    pub fn new(preamble: i32, window: i32, bytes: Vec<i64>) -> Self {
+        XmasCracker {
+            preamble,
+            window,
+            bytes,
+        }
+    }
+
+    pub fn find_first_invalid(&self) -> Option<i64> {
+        let mut current = (self.preamble) as usize;
+        let mut valid = true;
+
+        while valid && current < self.bytes.len() {
+            let start = current - self.window as usize;
+            let end = current;
+            let window: Vec<i64> = self.bytes[start..end].to_vec();
+
+            valid = match XmasCracker::has_solution(window, self.bytes[current]) {
+                true => {
+                    current += 1;
+                    true
+                }
+                false => false,
+            }
+        }
+
+        match valid {
+            true => None,
+            false => Some(self.bytes[current]),
+        }
+    }
+
+    /// The run of two to fifty numbers adding up to `target`, shortest first, with the sum of
+    /// its smallest and largest numbers.  Fails if several runs of that length match.
+    pub fn find_contiguous_range(&self, target: i64) -> Result<(Vec<i64>, i64), Error> {
+        let mut done = false;
+        let mut range = None;
+        let mut window = 2;
+
+        while !done {
+            let candidates: Vec<Vec<i64>> = self
+                .bytes
+                .windows(window)
+                .map(|w| (w.iter().sum::<i64>(), w.to_vec()))
+                .filter(|w| w.0 == target)
+                .map(|w| w.1)
+                .collect();
+
+            done = match candidates.len() {
+                0 => {
+                    if window < 50 {
+                        window += 1;
+                        false
+                    } else {
+                        true
+                    }
+                }
+                1 => {
+                    let mut sorted = candidates[0].clone();
+                    sorted.sort_unstable();
+                    let crc = sorted[0] + sorted[sorted.len() - 1];
+                    range = Some((candidates[0].clone(), crc));
+                    true
+                }
+                _ => {
+                    return Err(Error::NoSolution(format!(
+                        "{} solutions found for target {}",
+                        candidates.len(),
+                        target
+                    )))
+                }
+            }
+        }
+
+        range.ok_or_else(|| Error::NoSolution(format!("no contiguous range sums to {}", target)))
+    }
+
+    fn has_solution(window: Vec<i64>, target: i64) -> bool {
+        for i in 0..window.len() {
+            for j in i + 1..window.len() {
+                if window[i] + window[j] == target {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    error::parse_lines(input, |l| {
+        l.parse::<i64>()
+            .map_err(|_| ParseError::new(1, l, "a whole number"))
+    })
+}
+
+/// The first number which isn't the sum of two of the `preamble` numbers before it.
+pub fn part1(numbers: &[i64], preamble: i32) -> Option<i64> {
+    XmasCracker::new(preamble, preamble, numbers.to_vec()).find_first_invalid()
+}
+
+/// The sum of the smallest and largest numbers in the range adding up to the part 1 answer.
+pub fn part2(numbers: &[i64], preamble: i32) -> Result<i64, Error> {
+    let cracker = XmasCracker::new(preamble, preamble, numbers.to_vec());
+    let target = cracker
+        .find_first_invalid()
+        .ok_or_else(|| Error::NoSolution("every number follows the rule".to_string()))?;
+    Ok(cracker.find_contiguous_range(target)?.1)
+}
+
+fn first_invalid(input: &str, preamble: i32) -> Result<Answer, Error> {
+    part1(&parse(input)?, preamble)
+        .ok_or_else(|| Error::NoSolution("every number follows the rule".to_string()))
+}
+
+fn encryption_weakness(input: &str, preamble: i32) -> Result<Answer, Error> {
+    part2(&parse(input)?, preamble)
+}
+
+fn solve_part1(input: &str) -> Result<Answer, Error> {
+    first_invalid(input, 25)
+}
+
+fn solve_part2(input: &str) -> Result<Answer, Error> {
+    encryption_weakness(input, 25)
+}
+
+// the examples only use a 5 number preamble
+fn example_part1(input: &str) -> Result<Answer, Error> {
+    first_invalid(input, 5)
+}
+
+fn example_part2(input: &str) -> Result<Answer, Error> {
+    encryption_weakness(input, 5)
+}
+
+pub static DAY: Day = Day {
+    number: 9,
+    title: "Encoding Error",
+    part1: Some(solve_part1),
+    part2: Some(solve_part2),
+    examples: &[
+        Example {
+            part: Part::One,
+            input: Input::Example(1),
+            expected: 127,
+            solver: Some(example_part1),
+        },
+        Example {
+            part: Part::Two,
+            input: Input::Example(1),
+            expected: 62,
+            solver: Some(example_part2),
+        },
+    ],
+};